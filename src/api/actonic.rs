@@ -0,0 +1,149 @@
+use super::adf;
+use super::backend::WorklogBackend;
+use super::jira::value_to_string;
+use super::Jira;
+use crate::models::{DateRange, WorkLog, WorkLogList, WorkLogState};
+use anyhow::Result;
+use chrono::{TimeZone, Utc};
+use reqwest::Method;
+use serde_json::Value;
+
+pub const ACTONIC_SEARCH_PATH: &str = "/actonic-tb/1.0/api/worklogs/search-issues";
+
+/// Worklogs from the Actonic Timesheet Builder plugin.
+pub struct Actonic;
+
+impl WorklogBackend for Actonic {
+    fn fetch_worklogs(&self, jira: &Jira, range: DateRange) -> Result<WorkLogList> {
+        let user_id = jira.get_user_id()?;
+
+        let request_body = serde_json::json!({
+            "startDate": range.from.format("%Y/%m/%d").to_string(),
+            "endDate":range.to.format("%Y/%m/%d").to_string(),
+            "worklogAuthorId": user_id,
+        });
+
+        let response = jira
+            .request(Method::POST, ACTONIC_SEARCH_PATH)
+            .json(&request_body)
+            .send()?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to fetch worklogs: {}", response.status());
+        }
+
+        Ok(parse_worklogs(
+            &response.json()?,
+            jira.identity_field(),
+            &user_id,
+        ))
+    }
+}
+
+fn parse_worklogs(data: &Value, identity_field: &str, user_id: &str) -> WorkLogList {
+    let mut entires: WorkLogList = Vec::new();
+    if let Some(issues) = data.get("issues").and_then(|i| i.as_array()) {
+        for issue in issues {
+            if let Some(worklogs) = issue
+                .get("worklog")
+                .and_then(|w| w.get("worklogs"))
+                .and_then(|w| w.as_array())
+            {
+                for log in worklogs {
+                    if let Some(author_key) = log
+                        .get("author")
+                        .and_then(|a| a.get(identity_field))
+                        .and_then(|k| k.as_str())
+                    {
+                        if author_key != user_id {
+                            continue;
+                        }
+
+                        let day = log
+                            .get("started")
+                            .and_then(|timestamp| timestamp.as_i64())
+                            .and_then(|ts| Utc.timestamp_millis_opt(ts).single())
+                            .map(|time| time.to_utc());
+
+                        if let Some(day) = day {
+                            let time_spent = log
+                                .get("timeSpent")
+                                .and_then(|t| t.as_str())
+                                .unwrap_or("")
+                                .to_string();
+
+                            let time_spent_seconds = log
+                                .get("timeSpentSeconds")
+                                .and_then(|t| t.as_u64())
+                                .unwrap_or(0);
+
+                            let task = log
+                                .get("issueKey")
+                                .and_then(|k| k.as_str())
+                                .unwrap_or("")
+                                .to_string();
+
+                            let comment = adf::comment_text(log.get("comment"));
+
+                            entires.push(WorkLog {
+                                id: value_to_string(log.get("id")),
+                                issue_id: value_to_string(log.get("issueId")),
+                                day,
+                                task,
+                                time_spent,
+                                time_spent_seconds,
+                                comment,
+                                state: WorkLogState::Logged,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+    entires
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_worklogs_skips_other_authors() {
+        let data = serde_json::json!({
+            "issues": [{
+                "worklog": {
+                    "worklogs": [
+                        {
+                            "id": 10,
+                            "issueId": "200",
+                            "issueKey": "XX-1",
+                            "author": { "key": "me" },
+                            "started": 1741161600000i64,
+                            "timeSpent": "2h",
+                            "timeSpentSeconds": 7200,
+                            "comment": "review"
+                        },
+                        {
+                            "id": 11,
+                            "issueKey": "XX-1",
+                            "author": { "key": "someone" },
+                            "started": 1741161600000i64,
+                            "timeSpent": "1h",
+                            "timeSpentSeconds": 3600
+                        }
+                    ]
+                }
+            }]
+        });
+
+        let worklogs = parse_worklogs(&data, "key", "me");
+        assert_eq!(worklogs.len(), 1);
+        assert_eq!(worklogs[0].id, "10");
+        assert_eq!(worklogs[0].issue_id, "200");
+        assert_eq!(worklogs[0].task, "XX-1");
+        assert_eq!(worklogs[0].time_spent_seconds, 7200);
+        assert_eq!(worklogs[0].comment, Some("review".to_string()));
+        assert_eq!(worklogs[0].day.format("%Y-%m-%d").to_string(), "2025-03-05");
+    }
+}
@@ -0,0 +1,85 @@
+//! Minimal Atlassian Document Format support for worklog comments, which the
+//! v3 REST API sends and expects instead of plain strings.
+use serde_json::Value;
+
+/// Wraps plain text in an ADF document, one paragraph per line.
+pub fn from_text(text: &str) -> Value {
+    let paragraphs: Vec<Value> = text
+        .lines()
+        .map(|line| {
+            if line.is_empty() {
+                serde_json::json!({ "type": "paragraph", "content": [] })
+            } else {
+                serde_json::json!({
+                    "type": "paragraph",
+                    "content": [{ "type": "text", "text": line }]
+                })
+            }
+        })
+        .collect();
+
+    serde_json::json!({
+        "type": "doc",
+        "version": 1,
+        "content": paragraphs,
+    })
+}
+
+/// Flattens an ADF document back to plain text, one line per block.
+pub fn to_text(doc: &Value) -> String {
+    match doc.get("type").and_then(|t| t.as_str()) {
+        Some("text") => doc
+            .get("text")
+            .and_then(|t| t.as_str())
+            .unwrap_or("")
+            .to_string(),
+        Some("hardBreak") => "\n".to_string(),
+        _ => {
+            let children = doc
+                .get("content")
+                .and_then(|c| c.as_array())
+                .map(|c| c.iter().map(to_text).collect::<Vec<_>>())
+                .unwrap_or_default();
+            match doc.get("type").and_then(|t| t.as_str()) {
+                Some("doc") | Some("bulletList") | Some("orderedList") => children.join("\n"),
+                _ => children.concat(),
+            }
+        }
+    }
+}
+
+/// Reads a worklog comment that is either a plain string (v2) or ADF (v3).
+pub fn comment_text(comment: Option<&Value>) -> Option<String> {
+    match comment? {
+        Value::String(s) => Some(s.clone()),
+        doc @ Value::Object(_) => Some(to_text(doc)),
+        _ => None,
+    }
+    .filter(|c| !c.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adf_roundtrip() {
+        let doc = from_text("retro\nplanning");
+        assert_eq!(doc["content"].as_array().unwrap().len(), 2);
+        assert_eq!(to_text(&doc), "retro\nplanning");
+    }
+
+    #[test]
+    fn test_comment_text() {
+        assert_eq!(
+            comment_text(Some(&Value::String("retro".to_string()))),
+            Some("retro".to_string())
+        );
+        assert_eq!(
+            comment_text(Some(&from_text("retro"))),
+            Some("retro".to_string())
+        );
+        assert_eq!(comment_text(Some(&Value::String(String::new()))), None);
+        assert_eq!(comment_text(None), None);
+    }
+}
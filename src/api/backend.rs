@@ -0,0 +1,38 @@
+use super::Jira;
+use crate::models::{DateRange, WorkLogList};
+use anyhow::Result;
+use chrono::NaiveDate;
+
+/// Storage of the current user's worklogs. Writes default to native Jira
+/// worklogs on the issue, which is where Actonic reads them from as well.
+/// `log_worktime` returns the id of the created worklog.
+pub trait WorklogBackend {
+    fn fetch_worklogs(&self, jira: &Jira, range: DateRange) -> Result<WorkLogList>;
+
+    fn log_worktime(
+        &self,
+        jira: &Jira,
+        task: &str,
+        time_spent: u64,
+        date: &NaiveDate,
+        comment: Option<String>,
+    ) -> Result<String> {
+        jira.add_issue_worklog(task, time_spent, date, comment)
+    }
+
+    fn update_worklog(
+        &self,
+        jira: &Jira,
+        task: &str,
+        worklog_id: &str,
+        time_spent: u64,
+        date: &NaiveDate,
+        comment: Option<String>,
+    ) -> Result<bool> {
+        jira.update_issue_worklog(task, worklog_id, time_spent, date, comment)
+    }
+
+    fn delete_worklog(&self, jira: &Jira, task: &str, worklog_id: &str) -> Result<bool> {
+        jira.delete_issue_worklog(task, worklog_id)
+    }
+}
@@ -1,15 +1,47 @@
-use crate::models::{DateRange, Task, WorkLog, WorkLogList};
+use super::actonic::{Actonic, ACTONIC_SEARCH_PATH};
+use super::adf;
+use super::backend::WorklogBackend;
+use super::native::Native;
+use crate::cache::Cache;
+use crate::config::WorklogBackendKind;
+use crate::duration::WorkUnits;
+use crate::models::{DateRange, Task, WorkLog, WorkLogList, WorkLogListExt};
 use anyhow::Result;
-use chrono::{NaiveDate, TimeZone, Utc};
-use reqwest::blocking::Client;
+use chrono::{NaiveDate, Utc};
+use colored::Colorize;
+use reqwest::blocking::{Client, RequestBuilder};
+use reqwest::{Method, StatusCode};
 use serde::Serialize;
 use serde_json::Value;
+use std::cell::{OnceCell, RefCell};
+use std::collections::{BTreeMap, HashMap};
+
+const PAGE_SIZE: u64 = 50;
+const TASK_FIELDS: &str = "summary,status,project";
+pub const DEFAULT_TASK_JQL: &str = "assignee=currentUser() AND statusCategory!=Done";
 
 pub struct Jira {
     client: Client,
     url: String,
-    user_id: Option<String>,
-    token: String,
+    user_id: OnceCell<String>,
+    deployment: Deployment,
+    backend_kind: WorklogBackendKind,
+    backend: OnceCell<Box<dyn WorklogBackend>>,
+    task_jql: String,
+    /// Configured length of a working day, in seconds.
+    workday: Option<u64>,
+    work_units: OnceCell<WorkUnits>,
+    /// Set in dry-run mode: seconds that would have been logged per day.
+    dry_run: Option<RefCell<BTreeMap<NaiveDate, i64>>>,
+}
+
+/// How the Jira instance is hosted, which decides authentication,
+/// REST API version and user identity.
+pub enum Deployment {
+    /// Server / Data Center with a personal access token (bearer auth)
+    Server { token: String },
+    /// Jira Cloud with an email and API token (basic auth)
+    Cloud { email: String, token: String },
 }
 
 #[derive(Serialize)]
@@ -18,184 +50,687 @@ pub struct WorklogBody {
     #[serde(rename = "timeSpentSeconds")]
     time_spent_seconds: u64,
     #[serde(skip_serializing_if = "Option::is_none")]
-    comment: Option<String>,
+    comment: Option<Value>,
 }
 
 impl Jira {
-    pub fn new(url: String, token: String) -> Self {
+    pub fn new(url: String, deployment: Deployment, backend_kind: WorklogBackendKind) -> Self {
         Jira {
             client: Client::builder()
                 .timeout(std::time::Duration::from_secs(5))
                 .build()
                 .expect("Failed to build client"),
             url,
-            user_id: None,
-            token,
+            user_id: OnceCell::new(),
+            deployment,
+            backend_kind,
+            backend: OnceCell::new(),
+            task_jql: DEFAULT_TASK_JQL.to_string(),
+            workday: None,
+            work_units: OnceCell::new(),
+            dry_run: None,
+        }
+    }
+
+    /// Prints mutating requests instead of sending them.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run.then(|| RefCell::new(BTreeMap::new()));
+        self
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run.is_some()
+    }
+
+    /// In dry-run mode prints the request that would be sent and returns
+    /// true, in which case the caller must not send it.
+    pub fn intercept(&self, method: Method, url: &str, body: Option<&Value>) -> Result<bool> {
+        if !self.is_dry_run() {
+            return Ok(false);
+        }
+        println!(
+            "{} {} {}",
+            "[dry-run]".yellow(),
+            method.as_str().bold(),
+            url
+        );
+        if let Some(body) = body {
+            println!("{}", serde_json::to_string_pretty(body)?);
+        }
+        Ok(true)
+    }
+
+    /// Day totals as they would be after the dry run's changes.
+    pub fn dry_run_totals(&self) -> Result<Vec<(NaiveDate, u64, u64)>> {
+        let Some(changes) = &self.dry_run else {
+            return Ok(Vec::new());
+        };
+        let changes = changes.borrow();
+        let (Some(&from), Some(&to)) = (changes.keys().next(), changes.keys().next_back()) else {
+            return Ok(Vec::new());
+        };
+        let worklogs = self.fetch_worklogs(DateRange { from, to })?;
+        Ok(changes
+            .iter()
+            .map(|(date, change)| {
+                let logged = worklogs.logged_on(*date);
+                (*date, logged, logged.saturating_add_signed(*change))
+            })
+            .collect())
+    }
+
+    pub fn with_task_jql(mut self, task_jql: String) -> Self {
+        self.task_jql = task_jql;
+        self
+    }
+
+    pub fn with_workday(mut self, workday: Option<u64>) -> Self {
+        self.workday = workday;
+        self
+    }
+
+    /// Lengths of `d` and `w` in durations: the configured workday, otherwise
+    /// Jira's time tracking settings, falling back to 8h days and 5-day weeks.
+    pub fn work_units(&self) -> WorkUnits {
+        *self.work_units.get_or_init(|| match self.workday {
+            Some(day) => WorkUnits { day, week: day * 5 },
+            None => self.fetch_work_units().unwrap_or_default(),
+        })
+    }
+
+    fn fetch_work_units(&self) -> Result<WorkUnits> {
+        let response = self
+            .request(Method::GET, &self.api_path("/configuration"))
+            .send()?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to fetch Jira configuration: {}", response.status());
         }
+
+        let configuration: Value = response.json()?;
+        let tracking = configuration
+            .get("timeTrackingConfiguration")
+            .ok_or_else(|| anyhow::anyhow!("Time tracking is disabled"))?;
+        let hours = tracking.get("workingHoursPerDay").and_then(|h| h.as_f64());
+        let days = tracking.get("workingDaysPerWeek").and_then(|d| d.as_f64());
+        let (Some(hours), Some(days)) = (hours, days) else {
+            anyhow::bail!("No working hours in the time tracking configuration");
+        };
+        let day = (hours * 3600.0).round() as u64;
+        Ok(WorkUnits {
+            day,
+            week: (days * day as f64).round() as u64,
+        })
     }
 
     fn build_url(&self, path: &str) -> String {
         format!("https://{}/rest{}", self.url, path)
     }
 
+    pub fn is_cloud(&self) -> bool {
+        matches!(self.deployment, Deployment::Cloud { .. })
+    }
+
+    /// Path of a core REST API resource, v3 on Cloud and v2 elsewhere.
+    pub fn api_path(&self, path: &str) -> String {
+        format!("/api/{}{}", if self.is_cloud() { 3 } else { 2 }, path)
+    }
+
+    /// Field identifying a user: `accountId` on Cloud, `key` on Server.
+    pub fn identity_field(&self) -> &'static str {
+        if self.is_cloud() {
+            "accountId"
+        } else {
+            "key"
+        }
+    }
+
+    pub fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let request = self.client.request(method, self.build_url(path));
+        match &self.deployment {
+            Deployment::Server { token } => request.bearer_auth(token),
+            Deployment::Cloud { email, token } => request.basic_auth(email, Some(token)),
+        }
+    }
+
     pub fn get_user_id(&self) -> Result<String> {
-        if let Some(user_id) = &self.user_id {
+        if let Some(user_id) = self.user_id.get() {
             return Ok(user_id.clone());
         }
 
         let response = self
-            .client
-            .get(self.build_url("/api/2/myself"))
-            .bearer_auth(&self.token)
+            .request(Method::GET, &self.api_path("/myself"))
             .send()?;
 
         if response.status().is_success() {
             let users: Value = response.json()?;
-            Ok(users["key"]
+            let user_id = users[self.identity_field()]
                 .as_str()
                 .map(|s| s.to_string())
-                .ok_or_else(|| anyhow::anyhow!("User key not found in response"))?)
+                .ok_or_else(|| anyhow::anyhow!("User key not found in response"))?;
+            Ok(self.user_id.get_or_init(|| user_id).clone())
         } else {
             Err(anyhow::anyhow!("Failed to fetch user ID"))
         }
     }
 
+    fn worklog_body(&self, time_spent: u64, date: &NaiveDate, comment: Option<String>) -> Value {
+        let worklog_body = WorklogBody {
+            started: format!("{}T08:00:00.000+0000", date.format("%Y-%m-%d")),
+            time_spent_seconds: time_spent,
+            comment: comment.map(|c| {
+                if self.is_cloud() {
+                    adf::from_text(&c)
+                } else {
+                    Value::String(c)
+                }
+            }),
+        };
+
+        serde_json::json!(worklog_body)
+    }
+
     pub fn log_worktime(
         &self,
         task: &str,
         time_spent: u64,
         date: &NaiveDate,
         comment: Option<String>,
+    ) -> Result<String> {
+        let worklog_id = self
+            .backend()?
+            .log_worktime(self, task, time_spent, date, comment)?;
+        self.track(*date, time_spent as i64);
+        Ok(worklog_id)
+    }
+
+    pub fn update_worklog(
+        &self,
+        worklog: &WorkLog,
+        time_spent: u64,
+        date: &NaiveDate,
+        comment: Option<String>,
     ) -> Result<bool> {
-        let worklog_body = WorklogBody {
-            started: format!("{}T08:00:00.000+0000", date.format("%Y-%m-%d")),
-            time_spent_seconds: time_spent,
+        let updated = self.backend()?.update_worklog(
+            self,
+            worklog.issue(),
+            &worklog.id,
+            time_spent,
+            date,
             comment,
-        };
+        )?;
+        self.track(
+            worklog.day.date_naive(),
+            -(worklog.time_spent_seconds as i64),
+        );
+        self.track(*date, time_spent as i64);
+        Ok(updated)
+    }
 
-        let json_body = serde_json::json!(worklog_body);
-        let url = self.build_url(format!("/api/2/issue/{}/worklog", task).as_str());
+    /// Deletes a worklog of `time_spent` seconds logged on `date`.
+    pub fn delete_worklog(
+        &self,
+        task: &str,
+        worklog_id: &str,
+        date: NaiveDate,
+        time_spent: u64,
+    ) -> Result<bool> {
+        let deleted = self.backend()?.delete_worklog(self, task, worklog_id)?;
+        self.track(date, -(time_spent as i64));
+        Ok(deleted)
+    }
 
-        let response = self
-            .client
-            .post(&url)
-            .bearer_auth(&self.token)
-            .json(&json_body)
-            .send()?;
+    /// Keeps the change of a day's total for `dry_run_totals`.
+    fn track(&self, date: NaiveDate, seconds: i64) {
+        if let Some(changes) = &self.dry_run {
+            *changes.borrow_mut().entry(date).or_default() += seconds;
+        }
+    }
+
+    /// Creates a worklog on the issue itself (native Jira worklogs) and
+    /// returns its id.
+    pub fn add_issue_worklog(
+        &self,
+        task: &str,
+        time_spent: u64,
+        date: &NaiveDate,
+        comment: Option<String>,
+    ) -> Result<String> {
+        let json_body = self.worklog_body(time_spent, date, comment);
+        let path = self.api_path(&format!("/issue/{}/worklog", task));
+        if self.intercept(Method::POST, &self.build_url(&path), Some(&json_body))? {
+            return Ok(String::new());
+        }
+
+        let response = self.request(Method::POST, &path).json(&json_body).send()?;
 
         if response.status().is_success() {
-            Ok(true)
+            let worklog: Value = response.json()?;
+            Ok(value_to_string(worklog.get("id")))
         } else {
             Err(anyhow::anyhow!("Failed to log time: {}", response.text()?))
         }
     }
 
+    pub fn update_issue_worklog(
+        &self,
+        task: &str,
+        worklog_id: &str,
+        time_spent: u64,
+        date: &NaiveDate,
+        comment: Option<String>,
+    ) -> Result<bool> {
+        let json_body = self.worklog_body(time_spent, date, comment);
+        let path = self.api_path(&format!("/issue/{}/worklog/{}", task, worklog_id));
+        if self.intercept(Method::PUT, &self.build_url(&path), Some(&json_body))? {
+            return Ok(true);
+        }
+
+        let response = self.request(Method::PUT, &path).json(&json_body).send()?;
+
+        if response.status().is_success() {
+            Ok(true)
+        } else {
+            Err(anyhow::anyhow!(
+                "Failed to update worklog: {}",
+                response.text()?
+            ))
+        }
+    }
+
+    pub fn delete_issue_worklog(&self, task: &str, worklog_id: &str) -> Result<bool> {
+        let path = self.api_path(&format!("/issue/{}/worklog/{}", task, worklog_id));
+        if self.intercept(Method::DELETE, &self.build_url(&path), None)? {
+            return Ok(true);
+        }
+
+        let response = self.request(Method::DELETE, &path).send()?;
+
+        if response.status().is_success() {
+            Ok(true)
+        } else {
+            Err(anyhow::anyhow!(
+                "Failed to delete worklog: {}",
+                response.text()?
+            ))
+        }
+    }
+
     pub fn fetch_worklogs(&self, range: DateRange) -> Result<WorkLogList> {
-        let user_id = self.get_user_id()?;
-        let url = self.build_url("/actonic-tb/1.0/api/worklogs/search-issues");
+        self.backend()?.fetch_worklogs(self, range)
+    }
+
+    /// Runs a JQL search and follows its pagination: `startAt`/`total` on
+    /// Server, `nextPageToken` on Cloud.
+    pub fn search(&self, jql: &str, fields: &str) -> Result<Vec<Value>> {
+        let mut issues = Vec::new();
+        let mut next_page_token: Option<String> = None;
+        loop {
+            let mut query = vec![
+                ("jql", jql.to_string()),
+                ("fields", fields.to_string()),
+                ("maxResults", PAGE_SIZE.to_string()),
+            ];
+            let path = if self.is_cloud() {
+                if let Some(token) = &next_page_token {
+                    query.push(("nextPageToken", token.clone()));
+                }
+                self.api_path("/search/jql")
+            } else {
+                query.push(("startAt", issues.len().to_string()));
+                self.api_path("/search")
+            };
+
+            let response = self.request(Method::GET, &path).query(&query).send()?;
+
+            if !response.status().is_success() {
+                anyhow::bail!("Failed to fetch issues: {}", response.status());
+            }
+
+            let page: Value = response.json()?;
+            let page_issues = page
+                .get("issues")
+                .and_then(|i| i.as_array())
+                .ok_or_else(|| anyhow::anyhow!("No issues field in response"))?;
+            issues.extend(page_issues.iter().cloned());
+
+            let has_more = if self.is_cloud() {
+                next_page_token = page
+                    .get("nextPageToken")
+                    .and_then(|t| t.as_str())
+                    .map(|t| t.to_string());
+                next_page_token.is_some()
+            } else {
+                let total = page.get("total").and_then(|t| t.as_u64()).unwrap_or(0);
+                (issues.len() as u64) < total
+            };
+            if page_issues.is_empty() || !has_more {
+                return Ok(issues);
+            }
+        }
+    }
+
+    /// Fetches every worklog of the issue, following `startAt`/`total` pagination.
+    pub fn issue_worklogs(&self, issue: &str) -> Result<Vec<Value>> {
+        let mut worklogs = Vec::new();
+        loop {
+            let response = self
+                .request(
+                    Method::GET,
+                    &self.api_path(&format!("/issue/{}/worklog", issue)),
+                )
+                .query(&[
+                    ("startAt", worklogs.len().to_string()),
+                    ("maxResults", PAGE_SIZE.to_string()),
+                ])
+                .send()?;
+
+            if !response.status().is_success() {
+                anyhow::bail!(
+                    "Failed to fetch worklogs of {}: {}",
+                    issue,
+                    response.status()
+                );
+            }
 
-        let request_body = serde_json::json!({
-            "startDate": range.from.format("%Y/%m/%d").to_string(),
-            "endDate":range.to.format("%Y/%m/%d").to_string(),
-            "worklogAuthorId": user_id,
-        });
+            let page: Value = response.json()?;
+            let page_worklogs = page
+                .get("worklogs")
+                .and_then(|w| w.as_array())
+                .ok_or_else(|| anyhow::anyhow!("No worklogs field in response"))?;
+            let total = page.get("total").and_then(|t| t.as_u64()).unwrap_or(0);
+
+            worklogs.extend(page_worklogs.iter().cloned());
+            if page_worklogs.is_empty() || worklogs.len() as u64 >= total {
+                return Ok(worklogs);
+            }
+        }
+    }
+
+    /// Uses the given backend instead of resolving one from `backend_kind`.
+    pub fn with_backend(self, backend: Box<dyn WorklogBackend>) -> Self {
+        let _ = self.backend.set(backend);
+        self
+    }
+
+    fn backend(&self) -> Result<&dyn WorklogBackend> {
+        // Tempo Cloud identifies authors by Atlassian account id, which Server lacks
+        if matches!(self.backend_kind, WorklogBackendKind::Tempo) && !self.is_cloud() {
+            anyhow::bail!("Tempo backend is only supported on Jira Cloud");
+        }
+        if let Some(backend) = self.backend.get() {
+            return Ok(backend.as_ref());
+        }
+
+        let kind = match self.backend_kind {
+            // The Actonic endpoint used here is only available on Server / Data Center
+            WorklogBackendKind::Auto if self.is_cloud() => WorklogBackendKind::Native,
+            WorklogBackendKind::Auto => self.detect_backend()?,
+            kind => kind,
+        };
+        let backend: Box<dyn WorklogBackend> = match kind {
+            WorklogBackendKind::Actonic => Box::new(Actonic),
+            WorklogBackendKind::Tempo => {
+                anyhow::bail!("Tempo backend requires a token: jtime config --tempo-token <TOKEN>")
+            }
+            _ => Box::new(Native),
+        };
+        Ok(self.backend.get_or_init(|| backend).as_ref())
+    }
+
+    /// Probes the Actonic Timesheet endpoint, falling back to native worklogs
+    /// when the plugin is not installed. Other failures are not cached.
+    fn detect_backend(&self) -> Result<WorklogBackendKind> {
+        let cache = Cache::new(format!(
+            "worklog-backend-{}",
+            self.url.replace(|c: char| !c.is_ascii_alphanumeric(), "_")
+        ));
+        if let Some(cached) = cache.load()? {
+            match cached.as_str() {
+                "actonic" => return Ok(WorklogBackendKind::Actonic),
+                "native" => return Ok(WorklogBackendKind::Native),
+                _ => {}
+            }
+        }
+
+        // The endpoint only takes POST. Any answer but 404 means the plugin is
+        // there, even if it rejects the probe itself.
+        let today = Utc::now().format("%Y/%m/%d").to_string();
+        let response = self
+            .request(Method::POST, ACTONIC_SEARCH_PATH)
+            .json(&serde_json::json!({ "startDate": today, "endDate": today }))
+            .send()?;
+        let kind = match response.status() {
+            status if status.is_success() => WorklogBackendKind::Actonic,
+            StatusCode::BAD_REQUEST
+            | StatusCode::METHOD_NOT_ALLOWED
+            | StatusCode::UNSUPPORTED_MEDIA_TYPE => WorklogBackendKind::Actonic,
+            StatusCode::NOT_FOUND => WorklogBackendKind::Native,
+            status => anyhow::bail!(
+                "Failed to detect the worklog backend: {}. Set it with jtime config --worklog-backend",
+                status
+            ),
+        };
+        cache.save(&kind.to_string())?;
+        Ok(kind)
+    }
+
+    /// Resolves an issue key to its numeric id.
+    pub fn issue_id(&self, key: &str) -> Result<String> {
+        if key.chars().all(|c| c.is_ascii_digit()) {
+            return Ok(key.to_string());
+        }
 
         let response = self
-            .client
-            .post(url)
-            .bearer_auth(&self.token)
-            .json(&request_body)
+            .request(Method::GET, &self.api_path(&format!("/issue/{}", key)))
+            .query(&[("fields", "summary")])
             .send()?;
 
         if !response.status().is_success() {
-            anyhow::bail!("Failed to fetch worklogs: {}", response.status());
-        }
-
-        let data: Value = response.json()?;
-        let mut entires: WorkLogList = Vec::new();
-        if let Some(issues) = data.get("issues").and_then(|i| i.as_array()) {
-            for issue in issues {
-                if let Some(worklogs) = issue
-                    .get("worklog")
-                    .and_then(|w| w.get("worklogs"))
-                    .and_then(|w| w.as_array())
-                {
-                    for log in worklogs {
-                        if let Some(author_key) = log
-                            .get("author")
-                            .and_then(|a| a.get("key"))
-                            .and_then(|k| k.as_str())
-                        {
-                            if author_key != user_id {
-                                continue;
-                            }
-
-                            let day = log
-                                .get("started")
-                                .and_then(|timestamp| timestamp.as_i64())
-                                .and_then(|ts| Utc.timestamp_millis_opt(ts).single())
-                                .map(|time| time.to_utc());
-
-                            if let Some(day) = day {
-                                let time_spent = log
-                                    .get("timeSpent")
-                                    .and_then(|t| t.as_str())
-                                    .unwrap_or("")
-                                    .to_string();
-
-                                let task = log
-                                    .get("issueKey")
-                                    .and_then(|k| k.as_str())
-                                    .unwrap_or("")
-                                    .to_string();
-
-                                entires.push(WorkLog {
-                                    day,
-                                    task,
-                                    time_spent,
-                                });
-                            }
-                        }
-                    }
+            anyhow::bail!("Failed to fetch issue {}: {}", key, response.status());
+        }
+
+        let issue: Value = response.json()?;
+        Ok(value_to_string(issue.get("id")))
+    }
+
+    /// Maps numeric issue ids to issue keys.
+    pub fn issue_keys(&self, ids: &[String]) -> Result<HashMap<String, String>> {
+        let mut keys = HashMap::new();
+        for chunk in ids.chunks(PAGE_SIZE as usize) {
+            let jql = format!("id in ({})", chunk.join(","));
+            for issue in self.search(&jql, "summary")? {
+                if let Some(key) = issue.get("key").and_then(|k| k.as_str()) {
+                    keys.insert(value_to_string(issue.get("id")), key.to_string());
                 }
             }
         }
-        Ok(entires)
+        Ok(keys)
     }
 
+    /// Issues offered in the task picker, selected by the configured `task_jql`.
     pub fn actually_works(&self) -> Result<Vec<Task>> {
-        let url = self.build_url(
-            "/api/2/search?jql=assignee=currentUser()%20AND%20statusCategory!=Done&maxResults=50",
-        );
+        self.search(&self.task_jql, TASK_FIELDS)?
+            .iter()
+            .map(parse_task)
+            .collect()
+    }
 
-        let response = self.client.get(&url).bearer_auth(&self.token).send()?;
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to fetch issues: {}", response.status());
+    /// Fetches the given issues by key. Strings that are not issue keys are
+    /// left out, as is a single key that doesn't exist; with several keys Jira
+    /// rejects the whole search when one of them doesn't exist.
+    pub fn get_tasks(&self, keys: &[String]) -> Result<Vec<Task>> {
+        self.issues(keys, TASK_FIELDS)?
+            .iter()
+            .map(parse_task)
+            .collect()
+    }
+
+    /// Issues with the given keys: a single one fetched directly, several
+    /// through a search.
+    fn issues(&self, keys: &[String], fields: &str) -> Result<Vec<Value>> {
+        let keys: Vec<&String> = keys.iter().filter(|key| is_issue_key(key)).collect();
+        match keys.as_slice() {
+            [] => Ok(Vec::new()),
+            [key] => {
+                let response = self
+                    .request(Method::GET, &self.api_path(&format!("/issue/{}", key)))
+                    .query(&[("fields", fields)])
+                    .send()?;
+                match response.status() {
+                    StatusCode::NOT_FOUND => Ok(Vec::new()),
+                    status if status.is_success() => Ok(vec![response.json()?]),
+                    status => anyhow::bail!("Failed to fetch issue {}: {}", key, status),
+                }
+            }
+            keys => {
+                let keys: Vec<String> = keys.iter().map(|key| format!("\"{}\"", key)).collect();
+                self.search(&format!("key in ({})", keys.join(",")), fields)
+            }
         }
+    }
+
+    /// Epic of each issue that has one: the "Epic Link" field where the
+    /// instance has it (Server), otherwise a parent of type Epic (Cloud).
+    /// Sub-tasks take the epic of their parent.
+    pub fn epics(&self, keys: &[String]) -> Result<HashMap<String, String>> {
+        let epic_link = self.epic_link_field()?;
+        self.epics_by(keys, &epic_link)
+    }
 
-        let json: Value = response.json().expect("Failed to parse JSON");
+    fn epics_by(
+        &self,
+        keys: &[String],
+        epic_link: &Option<String>,
+    ) -> Result<HashMap<String, String>> {
+        let fields = match epic_link {
+            Some(field) => format!("parent,{}", field),
+            None => "parent".to_string(),
+        };
+        let mut epics = HashMap::new();
+        let mut parents = HashMap::new();
+        for issue in self.issues(keys, &fields)? {
+            let (Some(key), Some(fields)) = (
+                issue.get("key").and_then(|v| v.as_str()),
+                issue.get("fields"),
+            ) else {
+                continue;
+            };
+            let linked = epic_link
+                .as_ref()
+                .and_then(|field| fields.get(field))
+                .and_then(|v| v.as_str());
+            let parent = fields.get("parent");
+            let parent_key = parent.and_then(|p| p.get("key")).and_then(|v| v.as_str());
+            let parent_is_epic = parent
+                .and_then(|p| p.pointer("/fields/issuetype/name"))
+                .and_then(|v| v.as_str())
+                == Some("Epic");
+            match (linked, parent_key) {
+                (Some(epic), _) => epics.insert(key.to_string(), epic.to_string()),
+                (None, Some(parent)) if parent_is_epic => {
+                    epics.insert(key.to_string(), parent.to_string())
+                }
+                (None, Some(parent)) => parents.insert(key.to_string(), parent.to_string()),
+                (None, None) => None,
+            };
+        }
 
-        let issues = json
-            .get("issues")
-            .ok_or_else(|| anyhow::anyhow!("No issues field in response"))?
-            .as_array()
-            .ok_or_else(|| anyhow::anyhow!("Issues is not an array"))?;
-        issues
+        if !parents.is_empty() {
+            let mut parent_keys: Vec<String> = parents.values().cloned().collect();
+            parent_keys.sort();
+            parent_keys.dedup();
+            let parent_epics = self.epics_by(&parent_keys, epic_link)?;
+            for (key, parent) in parents {
+                if let Some(epic) = parent_epics.get(&parent) {
+                    epics.insert(key, epic.clone());
+                }
+            }
+        }
+        Ok(epics)
+    }
+
+    /// Id of the "Epic Link" custom field, which Jira Cloud no longer has.
+    fn epic_link_field(&self) -> Result<Option<String>> {
+        let response = self.request(Method::GET, &self.api_path("/field")).send()?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to fetch fields: {}", response.status());
+        }
+
+        let fields: Vec<Value> = response.json()?;
+        Ok(fields
             .iter()
-            .map(|issue| -> Result<Task, anyhow::Error> {
-                let id = issue
-                    .get("key")
-                    .and_then(|v| v.as_str())
-                    .map(|s| s.to_string())
-                    .ok_or_else(|| anyhow::anyhow!("Issue key not found"))?;
-                let name = issue
-                    .get("fields")
-                    .and_then(|f| f.get("summary"))
-                    .and_then(|v| v.as_str())
-                    .map(|s| s.to_string())
-                    .ok_or_else(|| anyhow::anyhow!("Summary not found"))?;
-                Ok(Task { id, name })
-            })
-            .collect()
+            .find(|f| f.get("name").and_then(|n| n.as_str()) == Some("Epic Link"))
+            .map(|f| value_to_string(f.get("id"))))
+    }
+}
+
+/// Whether the string looks like an issue key such as `XX-12`.
+fn is_issue_key(key: &str) -> bool {
+    regex::Regex::new(r"^[A-Z][A-Z0-9_]+-\d+$")
+        .unwrap()
+        .is_match(key)
+}
+
+fn parse_task(issue: &Value) -> Result<Task> {
+    let id = issue
+        .get("key")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| anyhow::anyhow!("Issue key not found"))?;
+    let name = issue
+        .get("fields")
+        .and_then(|f| f.get("summary"))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| anyhow::anyhow!("Summary not found"))?;
+    let field_name = |field: &str| {
+        issue
+            .get("fields")
+            .and_then(|f| f.get(field))
+            .and_then(|v| v.get("name"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+    };
+    Ok(Task {
+        id,
+        name,
+        status: field_name("status"),
+        project: field_name("project"),
+    })
+}
+
+/// Whether the request failed before Jira answered (no connection, timeout),
+/// as opposed to Jira rejecting it.
+pub fn is_network_error(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        cause
+            .downcast_ref::<reqwest::Error>()
+            .is_some_and(|e| e.is_connect() || e.is_timeout() || e.is_request())
+    })
+}
+
+/// Jira returns ids either as strings or as numbers depending on the endpoint.
+pub fn value_to_string(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Number(n)) => n.to_string(),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_issue_key() {
+        assert!(is_issue_key("XX-12"));
+        assert!(is_issue_key("AB2_C-1"));
+        assert!(!is_issue_key("xx-12"));
+        assert!(!is_issue_key("X-1"));
+        assert!(!is_issue_key("XX-1) OR (project = YY"));
+        assert!(!is_issue_key("12345"));
     }
 }
@@ -1,5 +1,13 @@
+pub mod actonic;
+pub mod adf;
+pub mod backend;
 pub mod jira;
 pub use jira::Jira;
 
+pub mod native;
+
 pub mod nager;
 pub use nager::Nager;
+
+pub mod tempo;
+pub use tempo::Tempo;
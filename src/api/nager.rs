@@ -1,5 +1,7 @@
 use crate::cache::Cache;
+use crate::models::DateRange;
 use anyhow::Result;
+use chrono::Datelike;
 use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -78,4 +80,13 @@ impl Nager {
         }
         Ok(holidays_map)
     }
+
+    /// Holidays of every year the range touches.
+    pub fn get_holidays_map_for_range(&self, range: &DateRange) -> Result<HolidayMap> {
+        let mut holidays_map = HolidayMap::new();
+        for year in range.from.year()..=range.to.year() {
+            holidays_map.extend(self.get_all_holidays_map(year.to_string())?);
+        }
+        Ok(holidays_map)
+    }
 }
@@ -0,0 +1,122 @@
+use super::adf;
+use super::backend::WorklogBackend;
+use super::jira::value_to_string;
+use super::Jira;
+use crate::models::{DateRange, WorkLog, WorkLogList, WorkLogState};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+/// Worklogs read directly from Jira: a JQL search for issues with the
+/// current user's worklogs, then the worklogs of each issue.
+pub struct Native;
+
+impl WorklogBackend for Native {
+    fn fetch_worklogs(&self, jira: &Jira, range: DateRange) -> Result<WorkLogList> {
+        let user_id = jira.get_user_id()?;
+        let jql = format!(
+            "worklogAuthor = currentUser() AND worklogDate >= \"{}\" AND worklogDate <= \"{}\"",
+            range.from.format("%Y-%m-%d"),
+            range.to.format("%Y-%m-%d")
+        );
+
+        let mut entries: WorkLogList = Vec::new();
+        for issue in jira.search(&jql, "summary")? {
+            let key = issue
+                .get("key")
+                .and_then(|k| k.as_str())
+                .ok_or_else(|| anyhow::anyhow!("Issue key not found"))?;
+
+            entries.extend(
+                jira.issue_worklogs(key)?
+                    .iter()
+                    .filter(|log| author_id(log, jira.identity_field()) == Some(user_id.as_str()))
+                    .filter_map(|log| parse_worklog(log, key))
+                    .filter(|log| {
+                        let day = log.day.date_naive();
+                        range.from <= day && day <= range.to
+                    }),
+            );
+        }
+        entries.sort_by_key(|log| log.day);
+        Ok(entries)
+    }
+}
+
+fn author_id<'a>(log: &'a Value, identity_field: &str) -> Option<&'a str> {
+    log.get("author")
+        .and_then(|a| a.get(identity_field))
+        .and_then(|k| k.as_str())
+}
+
+fn parse_worklog(log: &Value, key: &str) -> Option<WorkLog> {
+    let day = log
+        .get("started")
+        .and_then(|s| s.as_str())
+        .and_then(|s| DateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f%z").ok())
+        .map(|time| time.with_timezone(&Utc))?;
+
+    Some(WorkLog {
+        id: value_to_string(log.get("id")),
+        issue_id: value_to_string(log.get("issueId")),
+        day,
+        task: key.to_string(),
+        time_spent: log
+            .get("timeSpent")
+            .and_then(|t| t.as_str())
+            .unwrap_or("")
+            .to_string(),
+        time_spent_seconds: log
+            .get("timeSpentSeconds")
+            .and_then(|t| t.as_u64())
+            .unwrap_or(0),
+        comment: adf::comment_text(log.get("comment")),
+        state: WorkLogState::Logged,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_worklog_with_adf_comment() {
+        let log = serde_json::json!({
+            "id": "10",
+            "author": { "accountId": "5b10a2844c20165700ede21g" },
+            "started": "2025-03-05T08:00:00.000+0000",
+            "timeSpentSeconds": 900,
+            "comment": adf::from_text("standup")
+        });
+
+        assert_eq!(
+            author_id(&log, "accountId"),
+            Some("5b10a2844c20165700ede21g")
+        );
+        let worklog = parse_worklog(&log, "XX-1").unwrap();
+        assert_eq!(worklog.comment, Some("standup".to_string()));
+    }
+
+    #[test]
+    fn test_parse_worklog() {
+        let log = serde_json::json!({
+            "id": "10",
+            "issueId": "200",
+            "author": { "key": "me" },
+            "started": "2025-03-05T08:00:00.000+0000",
+            "timeSpent": "1h 30m",
+            "timeSpentSeconds": 5400,
+            "comment": "retro"
+        });
+
+        assert_eq!(author_id(&log, "key"), Some("me"));
+        assert_eq!(author_id(&log, "accountId"), None);
+        let worklog = parse_worklog(&log, "XX-1").unwrap();
+        assert_eq!(worklog.id, "10");
+        assert_eq!(worklog.issue_id, "200");
+        assert_eq!(worklog.task, "XX-1");
+        assert_eq!(worklog.time_spent_seconds, 5400);
+        assert_eq!(worklog.comment, Some("retro".to_string()));
+        assert_eq!(worklog.day.format("%Y-%m-%d").to_string(), "2025-03-05");
+    }
+}
@@ -0,0 +1,290 @@
+use super::backend::WorklogBackend;
+use super::jira::value_to_string;
+use super::Jira;
+use crate::duration::format_time;
+use crate::models::{DateRange, WorkLog, WorkLogList, WorkLogState};
+use anyhow::Result;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use reqwest::blocking::{Client, RequestBuilder};
+use reqwest::Method;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+const DEFAULT_TEMPO_URL: &str = "https://api.tempo.io/4";
+const PAGE_SIZE: u64 = 50;
+
+/// Worklogs stored in Tempo Timesheets. Jira is still used to resolve the
+/// current user and to map between issue keys and issue ids.
+pub struct Tempo {
+    client: Client,
+    url: String,
+    token: String,
+    attributes: BTreeMap<String, String>,
+}
+
+#[derive(Serialize)]
+struct TempoWorklogBody {
+    #[serde(rename = "authorAccountId")]
+    author_account_id: String,
+    #[serde(rename = "issueId")]
+    issue_id: u64,
+    #[serde(rename = "timeSpentSeconds")]
+    time_spent_seconds: u64,
+    #[serde(rename = "startDate")]
+    start_date: String,
+    #[serde(rename = "startTime")]
+    start_time: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    attributes: Vec<TempoAttribute>,
+}
+
+#[derive(Serialize)]
+struct TempoAttribute {
+    key: String,
+    value: String,
+}
+
+impl Tempo {
+    pub fn new(url: Option<String>, token: String, attributes: BTreeMap<String, String>) -> Self {
+        Tempo {
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(5))
+                .build()
+                .expect("Failed to build client"),
+            url: url
+                .unwrap_or_else(|| DEFAULT_TEMPO_URL.to_string())
+                .trim_end_matches('/')
+                .to_string(),
+            token,
+            attributes,
+        }
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.client
+            .request(method, format!("{}{}", self.url, path))
+            .bearer_auth(&self.token)
+    }
+
+    fn worklog_body(
+        &self,
+        jira: &Jira,
+        task: &str,
+        time_spent: u64,
+        date: &NaiveDate,
+        comment: Option<String>,
+    ) -> Result<TempoWorklogBody> {
+        Ok(TempoWorklogBody {
+            author_account_id: jira.get_user_id()?,
+            issue_id: jira
+                .issue_id(task)?
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Invalid issue id for {}", task))?,
+            time_spent_seconds: time_spent,
+            start_date: date.format("%Y-%m-%d").to_string(),
+            start_time: "08:00:00".to_string(),
+            description: comment,
+            attributes: self
+                .attributes
+                .iter()
+                .map(|(key, value)| TempoAttribute {
+                    key: key.clone(),
+                    value: value.clone(),
+                })
+                .collect(),
+        })
+    }
+}
+
+impl WorklogBackend for Tempo {
+    fn fetch_worklogs(&self, jira: &Jira, range: DateRange) -> Result<WorkLogList> {
+        let account_id = jira.get_user_id()?;
+
+        let mut results: Vec<Value> = Vec::new();
+        loop {
+            let response = self
+                .request(Method::GET, &format!("/worklogs/user/{}", account_id))
+                .query(&[
+                    ("from", range.from.format("%Y-%m-%d").to_string()),
+                    ("to", range.to.format("%Y-%m-%d").to_string()),
+                    ("offset", results.len().to_string()),
+                    ("limit", PAGE_SIZE.to_string()),
+                ])
+                .send()?;
+
+            if !response.status().is_success() {
+                anyhow::bail!("Failed to fetch Tempo worklogs: {}", response.status());
+            }
+
+            let page: Value = response.json()?;
+            let page_results = page
+                .get("results")
+                .and_then(|r| r.as_array())
+                .ok_or_else(|| anyhow::anyhow!("No results field in Tempo response"))?;
+            results.extend(page_results.iter().cloned());
+
+            let has_next = page
+                .get("metadata")
+                .and_then(|m| m.get("next"))
+                .is_some_and(|n| !n.is_null());
+            if page_results.is_empty() || !has_next {
+                break;
+            }
+        }
+
+        let mut issue_ids: Vec<String> = results
+            .iter()
+            .map(|log| value_to_string(log.get("issue").and_then(|i| i.get("id"))))
+            .filter(|id| !id.is_empty())
+            .collect();
+        issue_ids.sort();
+        issue_ids.dedup();
+        let keys = jira.issue_keys(&issue_ids)?;
+
+        Ok(results
+            .iter()
+            .filter_map(|log| parse_worklog(log, &keys))
+            .collect())
+    }
+
+    fn log_worktime(
+        &self,
+        jira: &Jira,
+        task: &str,
+        time_spent: u64,
+        date: &NaiveDate,
+        comment: Option<String>,
+    ) -> Result<String> {
+        let body = self.worklog_body(jira, task, time_spent, date, comment)?;
+        let url = format!("{}/worklogs", self.url);
+        if jira.intercept(Method::POST, &url, Some(&serde_json::to_value(&body)?))? {
+            return Ok(String::new());
+        }
+        let response = self.request(Method::POST, "/worklogs").json(&body).send()?;
+
+        if response.status().is_success() {
+            let worklog: Value = response.json()?;
+            Ok(value_to_string(worklog.get("tempoWorklogId")))
+        } else {
+            Err(anyhow::anyhow!("Failed to log time: {}", response.text()?))
+        }
+    }
+
+    fn update_worklog(
+        &self,
+        jira: &Jira,
+        task: &str,
+        worklog_id: &str,
+        time_spent: u64,
+        date: &NaiveDate,
+        comment: Option<String>,
+    ) -> Result<bool> {
+        let body = self.worklog_body(jira, task, time_spent, date, comment)?;
+        let url = format!("{}/worklogs/{}", self.url, worklog_id);
+        if jira.intercept(Method::PUT, &url, Some(&serde_json::to_value(&body)?))? {
+            return Ok(true);
+        }
+        let response = self
+            .request(Method::PUT, &format!("/worklogs/{}", worklog_id))
+            .json(&body)
+            .send()?;
+
+        if response.status().is_success() {
+            Ok(true)
+        } else {
+            Err(anyhow::anyhow!(
+                "Failed to update worklog: {}",
+                response.text()?
+            ))
+        }
+    }
+
+    fn delete_worklog(&self, jira: &Jira, _task: &str, worklog_id: &str) -> Result<bool> {
+        let url = format!("{}/worklogs/{}", self.url, worklog_id);
+        if jira.intercept(Method::DELETE, &url, None)? {
+            return Ok(true);
+        }
+        let response = self
+            .request(Method::DELETE, &format!("/worklogs/{}", worklog_id))
+            .send()?;
+
+        if response.status().is_success() {
+            Ok(true)
+        } else {
+            Err(anyhow::anyhow!(
+                "Failed to delete worklog: {}",
+                response.text()?
+            ))
+        }
+    }
+}
+
+fn parse_worklog(log: &Value, keys: &HashMap<String, String>) -> Option<WorkLog> {
+    let date = log
+        .get("startDate")
+        .and_then(|d| d.as_str())
+        .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())?;
+    let time = log
+        .get("startTime")
+        .and_then(|t| t.as_str())
+        .and_then(|t| NaiveTime::parse_from_str(t, "%H:%M:%S").ok())
+        .unwrap_or_default();
+    let issue_id = value_to_string(log.get("issue").and_then(|i| i.get("id")));
+    let time_spent_seconds = log
+        .get("timeSpentSeconds")
+        .and_then(|t| t.as_u64())
+        .unwrap_or(0);
+
+    Some(WorkLog {
+        id: value_to_string(log.get("tempoWorklogId")),
+        task: keys
+            .get(&issue_id)
+            .cloned()
+            .unwrap_or_else(|| issue_id.clone()),
+        issue_id,
+        day: NaiveDateTime::new(date, time).and_utc(),
+        time_spent: format_time(time_spent_seconds),
+        time_spent_seconds,
+        comment: log
+            .get("description")
+            .and_then(|d| d.as_str())
+            .filter(|d| !d.is_empty())
+            .map(|d| d.to_string()),
+        state: WorkLogState::Logged,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_worklog() {
+        let log = serde_json::json!({
+            "tempoWorklogId": 126,
+            "issue": { "id": 10100 },
+            "timeSpentSeconds": 5400,
+            "startDate": "2025-03-05",
+            "startTime": "09:30:00",
+            "description": "review",
+            "author": { "accountId": "me" },
+            "attributes": { "values": [{ "key": "_Account_", "value": "ACME" }] }
+        });
+        let keys = HashMap::from([("10100".to_string(), "XX-1".to_string())]);
+
+        let worklog = parse_worklog(&log, &keys).unwrap();
+        assert_eq!(worklog.id, "126");
+        assert_eq!(worklog.issue_id, "10100");
+        assert_eq!(worklog.task, "XX-1");
+        assert_eq!(worklog.time_spent, "1h30m");
+        assert_eq!(worklog.time_spent_seconds, 5400);
+        assert_eq!(worklog.comment, Some("review".to_string()));
+        assert_eq!(
+            worklog.day.format("%Y-%m-%d %H:%M").to_string(),
+            "2025-03-05 09:30"
+        );
+    }
+}
@@ -1,12 +1,14 @@
-use crate::api::{Jira, Nager};
-use crate::cli::{Cli, Commands};
+use crate::api::{Jira, Nager, Tempo};
+use crate::cli::{Cli, Commands, OffCommands, RecurCommands, TemplateCommands};
 use crate::commands;
-use crate::config::Config;
+use crate::config::{Config, WorklogBackendKind};
+use crate::duration::format_time;
 use anyhow::Result;
 use colored::Colorize;
+use std::cell::OnceCell;
 
 pub struct App {
-    api: Jira,
+    api: OnceCell<Jira>,
     nager: Nager,
     config: Config,
 }
@@ -14,66 +16,199 @@ pub struct App {
 impl App {
     pub fn new() -> Result<Self> {
         let config = Config::load()?;
-        let api = Jira::new(config.jira_url.clone(), config.jira_token.clone());
         let nager = Nager::new(config.nager_url.clone(), config.nager_country_code.clone());
-        Ok(Self { api, nager, config })
+        Ok(Self {
+            api: OnceCell::new(),
+            nager,
+            config,
+        })
+    }
+
+    /// Jira client, built on first use so that `jtime config` can still fix
+    /// settings it would reject.
+    fn api(&self, cli: &Cli) -> Result<&Jira> {
+        if let Some(api) = self.api.get() {
+            return Ok(api);
+        }
+        let config = &self.config;
+        let mut api = Jira::new(
+            config.jira_url.clone(),
+            config.deployment()?,
+            config.worklog_backend,
+        )
+        .with_task_jql(config.task_jql(cli.profile.as_deref())?)
+        .with_workday(config.workday()?)
+        .with_dry_run(cli.dry_run);
+        if let (WorklogBackendKind::Tempo, Some(token)) =
+            (config.worklog_backend, config.tempo_token.clone())
+        {
+            api = api.with_backend(Box::new(Tempo::new(
+                config.tempo_url.clone(),
+                token,
+                config.tempo_attributes.clone(),
+            )));
+        }
+        Ok(self.api.get_or_init(|| api))
     }
 
     pub fn run(&self, cli: &Cli) -> Result<()> {
         match &cli.command {
-            Commands::Log {
-                task,
+            Commands::Log(args) => {
+                commands::log::execute(&self.config, self.api(cli)?, &self.nager, args)?;
+            }
+            Commands::Edit {
+                day,
                 time,
+                date,
+                comment,
+                yes,
+            } => commands::edit::execute(self.api(cli)?, day, time, date, comment, yes)?,
+            Commands::Delete { day, yes } => commands::delete::execute(self.api(cli)?, day, yes)?,
+            Commands::Sync { discard, yes } => {
+                commands::sync::execute(self.api(cli)?, discard, yes)?
+            }
+            Commands::Undo { number, yes } => commands::undo::undo(self.api(cli)?, number, yes)?,
+            Commands::History => commands::undo::history()?,
+            Commands::Fill {
+                day,
+                task,
+                target,
+                yes,
+            } => commands::fill::execute(
+                &self.config,
+                self.api(cli)?,
+                &self.nager,
                 day,
+                task,
+                target,
+                yes,
+            )?,
+            Commands::Start { task, comment } => {
+                commands::timer::start(task, comment, &cli.dry_run)?
+            }
+            Commands::Stop { yes } => {
+                commands::timer::stop(&self.config, self.api(cli)?, &self.nager, yes)?;
+            }
+            Commands::Switch { task, comment, yes } => commands::timer::switch(
+                &self.config,
+                self.api(cli)?,
+                &self.nager,
+                task,
                 comment,
                 yes,
-                option_time,
-                option_day,
-                option_comment,
-            } => {
-                let time = match option_time {
-                    None => time,
-                    Some(v) => v,
-                };
-                let day = match option_day {
-                    None => day,
-                    Some(v) => &Some(v.to_string()),
-                };
-                let comment = match option_comment {
-                    None => comment,
-                    Some(v) => &Some(v.to_string()),
-                };
-
-                commands::log::execute(&self.api, &self.nager, task, time, day, comment, yes)?
+            )?,
+            Commands::Status => commands::timer::status()?,
+            Commands::Import { file, yes } => {
+                commands::import::execute(&self.config, self.api(cli)?, &self.nager, file, yes)?
             }
-            Commands::Month { cache, month } => {
-                commands::month::execute(&self.config, &self.api, &self.nager, cache, month)?
+            Commands::Export {
+                from,
+                to,
+                format,
+                output,
+            } => commands::export::execute(self.api(cli)?, from, to, format, output)?,
+            Commands::Report {
+                from,
+                to,
+                group_by,
+                summary,
+            } => commands::report::execute(self.api(cli)?, from, to, group_by, summary)?,
+            Commands::Template { command } => match command {
+                TemplateCommands::Add {
+                    name,
+                    task,
+                    time,
+                    comment,
+                    ask_comment,
+                } => commands::template::add(
+                    self.config.clone(),
+                    name,
+                    task,
+                    time,
+                    comment,
+                    ask_comment,
+                )?,
+                TemplateCommands::Remove { name } => {
+                    commands::template::remove(self.config.clone(), name)?
+                }
+                TemplateCommands::List => commands::template::list(&self.config)?,
+            },
+            Commands::Recur { command } => match command {
+                RecurCommands::Apply { month, year, yes } => commands::recur::apply(
+                    &self.config,
+                    self.api(cli)?,
+                    &self.nager,
+                    month,
+                    year,
+                    yes,
+                )?,
+                RecurCommands::Add {
+                    task,
+                    time,
+                    comment,
+                    on,
+                    every_weeks,
+                    start,
+                } => commands::recur::add(
+                    self.config.clone(),
+                    task,
+                    time,
+                    comment,
+                    on,
+                    every_weeks,
+                    start,
+                )?,
+                RecurCommands::Remove { index } => {
+                    commands::recur::remove(self.config.clone(), *index)?
+                }
+                RecurCommands::List => commands::recur::list(&self.config)?,
+            },
+            Commands::Off { command } => match command {
+                OffCommands::Add { day, kind, yes } => {
+                    commands::off::add(&self.config, self.api(cli)?, &self.nager, day, kind, yes)?
+                }
+                OffCommands::Remove { day } => commands::off::remove(day, &cli.dry_run)?,
+                OffCommands::List => commands::off::list()?,
+            },
+            Commands::Month(args) => {
+                commands::month::execute(&self.config, self.api(cli)?, &self.nager, args)?
             }
 
-            Commands::Week { cache, prev } => {
-                commands::week::execute(&self.config, &self.api, &self.nager, prev, cache)?
+            Commands::Week(args) => {
+                commands::week::execute(&self.config, self.api(cli)?, &self.nager, args)?
+            }
+            Commands::Config(args) => {
+                commands::config::execute(self.config.clone(), args, &cli.profile)?
             }
-            Commands::Config {
-                url,
-                token,
-                nager_url,
-                nager_country_code,
-                show_weekends,
-            } => commands::config::execute(
-                self.config.clone(),
-                url,
-                token,
-                nager_url,
-                nager_country_code,
-                show_weekends,
-            )?,
             Commands::Update => commands::update::execute()?,
         }
-        self.check_for_updates()
+        if let Some(api) = self.api.get().filter(|api| api.is_dry_run()) {
+            Self::print_dry_run_totals(api)?;
+        }
+        self.check_for_updates();
+        Ok(())
+    }
+
+    fn print_dry_run_totals(api: &Jira) -> Result<()> {
+        for (date, logged, total) in api.dry_run_totals()? {
+            println!(
+                "{} {}: {} -> {}",
+                "[dry-run]".yellow(),
+                date.format("%Y-%m-%d"),
+                format_time(logged),
+                format_time(total).green()
+            );
+        }
+        println!("{}", "Dry run, nothing was sent to Jira.".yellow());
+        Ok(())
     }
 
-    pub fn check_for_updates(&self) -> Result<()> {
-        let latest_version = commands::update::get_latest_version()?;
+    /// Mentions a newer release. Failing to reach GitHub is not an error of
+    /// the command that just ran, so it is ignored.
+    pub fn check_for_updates(&self) {
+        let Ok(latest_version) = commands::update::get_latest_version() else {
+            return;
+        };
         if commands::update::current_version() != latest_version {
             println!(
                 "{}",
@@ -86,6 +221,5 @@ impl App {
             );
             println!("Run `{}` to update.", "jtime update".green());
         }
-        Ok(())
     }
 }
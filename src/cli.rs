@@ -1,4 +1,7 @@
-use clap::{Parser, Subcommand};
+use crate::config::WorklogBackendKind;
+use crate::state::days_off::DayOffKind;
+use chrono::{Datelike, Months, NaiveDate, Utc};
+use clap::{Args, Parser, Subcommand};
 
 #[derive(Parser)]
 #[command(
@@ -10,6 +13,14 @@ use clap::{Parser, Subcommand};
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+
+    /// Configuration profile to use (e.g. support)
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+
+    /// Print the requests that would change Jira instead of sending them
+    #[arg(long, global = true, default_value_t = false)]
+    pub dry_run: bool,
 }
 
 #[derive(Subcommand)]
@@ -17,40 +28,222 @@ pub enum Commands {
     /// (Alias: l)
     /// Log time for a task
     /// Example: jtime log XX-1234 --day 2 --time 1h30m
+    /// Templates: jtime log @retro 14
     /// Default day is today
     /// Default time is 8h
     #[clap(alias = "l")]
-    Log {
-        /// Task identifier (eg. XX-1234)
-        task: Option<String>,
+    Log(LogArgs),
 
-        /// Day in this monh (eg. 2 or multiple days 2-5)
-        /// Default is current day
-        /// Example: 2
+    /// (Alias: e)
+    /// Edit an existing worklog
+    /// Example: jtime edit 2 --time 2h
+    /// Default day is today
+    #[clap(alias = "e")]
+    Edit {
+        /// Day of the worklog (eg. 2 or today)
+        #[arg(allow_negative_numbers = true)]
         day: Option<String>,
 
-        /// Time spent (e.g. 1h30m)
-        #[clap(default_value = "8h")]
-        time: String,
+        /// New time spent (e.g. 1h30m)
+        #[arg(short = 't', long = "time", value_name = "TIME")]
+        time: Option<String>,
+
+        /// Move the worklog to another day (e.g. 3)
+        #[arg(
+            short = 'd',
+            long = "date",
+            value_name = "DAY",
+            allow_negative_numbers = true
+        )]
+        date: Option<String>,
+
+        /// New comment for worklog
+        #[arg(short = 'c', long = "comment", value_name = "COMMENT")]
+        comment: Option<String>,
+
+        /// Skip confirmation
+        #[clap(long, default_value_t = false)]
+        yes: bool,
+    },
+
+    /// (Alias: rm)
+    /// Delete an existing worklog
+    /// Example: jtime delete 2
+    /// Default day is today
+    #[clap(alias = "rm")]
+    Delete {
+        /// Day of the worklog (eg. 2 or today)
+        #[arg(allow_negative_numbers = true)]
+        day: Option<String>,
+
+        /// Skip confirmation
+        #[clap(long, default_value_t = false)]
+        yes: bool,
+    },
+
+    /// Send worklogs queued while Jira was unreachable
+    /// Example: jtime sync
+    Sync {
+        /// Drop the queued worklogs instead of sending them
+        #[clap(long, default_value_t = false)]
+        discard: bool,
+
+        /// Skip confirmation
+        #[clap(long, default_value_t = false)]
+        yes: bool,
+    },
+
+    /// Delete the worklogs created by the last operation
+    /// Example: jtime undo
+    /// Undo an older one: jtime undo 3 (number from `jtime history`)
+    Undo {
+        /// Operation number from `jtime history`
+        number: Option<usize>,
+
+        /// Skip confirmation
+        #[clap(long, default_value_t = false)]
+        yes: bool,
+    },
+
+    /// List past operations that created worklogs
+    /// Example: jtime history
+    History,
+
+    /// (Alias: f)
+    /// Fill under-logged working days up to the daily target
+    /// Example: jtime fill 1-31 --task XX-1234
+    /// Weekends, holidays and days off are skipped
+    #[clap(alias = "f")]
+    Fill {
+        /// Day(s) to fill (eg. 2-5)
+        #[arg(allow_negative_numbers = true)]
+        day: String,
+
+        /// Task(s) to spread the missing time across, repeatable
+        /// Default is `fill_task` from config, otherwise a picker
+        #[arg(short = 't', long = "task", value_name = "TASK")]
+        task: Vec<String>,
+
+        /// Time expected per day (e.g. 8h)
+        /// Default is the expected time of the weekday from config
+        #[arg(long, value_name = "TIME")]
+        target: Option<String>,
+
+        /// Skip confirmation
+        #[clap(long, default_value_t = false)]
+        yes: bool,
+    },
+
+    /// Start a timer for a task, logged with `jtime stop`
+    /// Example: jtime start XX-1234 "code review"
+    Start {
+        /// Task identifier (eg. XX-1234)
+        task: String,
 
         /// Comment for worklog (e.g. retro)
         comment: Option<String>,
+    },
 
+    /// Stop the running timer and log the elapsed time
+    /// Example: jtime stop
+    Stop {
         /// Skip confirmation
         #[clap(long, default_value_t = false)]
         yes: bool,
+    },
 
-        /// Task but can be provided as option
-        #[arg(short = 'd', long = "day", value_name = "DAY")]
-        option_day: Option<String>,
+    /// Stop the running timer and start one for another task
+    /// Example: jtime switch XX-456
+    Switch {
+        /// Task identifier (eg. XX-1234)
+        task: String,
 
-        /// Task but can be provided as option
-        #[arg(short = 't', long = "time", value_name = "TIME")]
-        option_time: Option<String>,
+        /// Comment for worklog (e.g. retro)
+        comment: Option<String>,
 
-        /// Comment but can be provided as option
-        #[arg(short = 'c', long = "comment", value_name = "COMMENT")]
-        option_comment: Option<String>,
+        /// Skip confirmation
+        #[clap(long, default_value_t = false)]
+        yes: bool,
+    },
+
+    /// Show the running timer
+    /// Example: jtime status
+    Status,
+
+    /// Import worklogs from a CSV or JSON file
+    /// Example: jtime import week.csv
+    /// Rows have date, task, time (or duration) and an optional comment
+    Import {
+        /// Path to a .csv or .json file
+        file: String,
+
+        /// Skip confirmation
+        #[clap(long, default_value_t = false)]
+        yes: bool,
+    },
+
+    /// Export worklogs with per-issue and per-day totals
+    /// Example: jtime export --from 1 --to 15 --format md
+    /// Default range is the current month up to today
+    Export {
+        /// First day (eg. 1 or 01-03-2025)
+        #[clap(long, value_name = "DAY", allow_negative_numbers = true)]
+        from: Option<String>,
+
+        /// Last day (eg. 15 or today)
+        #[clap(long, value_name = "DAY", allow_negative_numbers = true)]
+        to: Option<String>,
+
+        /// Output format
+        #[clap(long, value_enum, default_value_t = ExportFormat::Csv)]
+        format: ExportFormat,
+
+        /// Write to a file instead of stdout
+        #[arg(short = 'o', long = "output", value_name = "FILE")]
+        output: Option<String>,
+    },
+
+    /// Time per issue, project, epic or day with percentages
+    /// Example: jtime report --from 1 --to 15 --group-by project
+    Report {
+        /// First day (eg. 1 or 01-03-2025)
+        #[clap(long, value_name = "DAY", allow_negative_numbers = true)]
+        from: Option<String>,
+
+        /// Last day (eg. 15 or today)
+        #[clap(long, value_name = "DAY", allow_negative_numbers = true)]
+        to: Option<String>,
+
+        /// What to add the time up by
+        #[clap(long, value_enum, default_value_t = GroupBy::Issue)]
+        group_by: GroupBy,
+
+        /// Show issue summaries (project names with --group-by project)
+        #[clap(long, default_value_t = false)]
+        summary: bool,
+    },
+
+    /// (Alias: t)
+    /// Manage worklog templates used as `jtime log @name`
+    /// Example: jtime template add retro TD-12 --time 1h --comment retro
+    #[clap(alias = "t")]
+    Template {
+        #[command(subcommand)]
+        command: TemplateCommands,
+    },
+
+    /// Recurring worklogs such as standups and ceremonies
+    /// Example: jtime recur apply --month
+    Recur {
+        #[command(subcommand)]
+        command: RecurCommands,
+    },
+
+    /// Manage local days off (vacation, sick leave)
+    /// Example: jtime off add 12-15 --kind vacation
+    Off {
+        #[command(subcommand)]
+        command: OffCommands,
     },
 
     /// (Alias: m)
@@ -58,61 +251,491 @@ pub enum Commands {
     /// Get for Febuary: jtime m --month 2
     /// Example: jtime m --cache
     #[clap(alias = "m")]
-    Month {
-        /// Use cached data
-        #[clap(long, default_value_t = false)]
-        cache: bool,
-
-        /// Month number (1-12)
-        /// Default is current month
-        /// Example: 2
-        #[clap(short, long)]
-        month: Option<u32>,
-    },
+    Month(MonthArgs),
 
     /// (Alias: w)
     /// List weekly time logs
     /// Example: jtime week
     #[clap(alias = "w")]
-    Week {
-        /// Use cached data
-        #[clap(long, short, default_value_t = false)]
-        cache: bool,
-
-        /// Previous week
-        #[clap(long, short, default_value_t = false)]
-        prev: bool,
-    },
+    Week(WeekArgs),
 
     /// (Alias: c)
     /// Show or set configuration
     /// Example: jtime config --url https://jira.com --token 123
     #[clap(alias = "c")]
-    Config {
-        /// Jira URL
-        #[clap(long)]
-        url: Option<String>,
+    Config(Box<ConfigArgs>),
 
-        /// Jira token
-        #[clap(long)]
-        token: Option<String>,
+    /// (Alias: u)
+    /// Update JTime to the latest version
+    /// Example: jtime update
+    #[clap(alias = "u")]
+    Update,
+}
 
-        /// Nager url
-        #[clap(long)]
-        nager_url: Option<Option<String>>,
+#[derive(Subcommand)]
+pub enum TemplateCommands {
+    /// Add or replace a template
+    /// Example: jtime template add td TD-1 --comment "review, planning" --ask-comment
+    Add {
+        /// Template name (eg. retro)
+        name: String,
 
-        /// Country code for nager
-        #[clap(long)]
-        nager_country_code: Option<Option<String>>,
+        /// Task identifier (eg. XX-1234)
+        task: String,
+
+        /// Time spent (e.g. 1h30m)
+        #[arg(short = 't', long = "time", value_name = "TIME")]
+        time: Option<String>,
+
+        /// Comment for worklog (e.g. retro)
+        #[arg(short = 'c', long = "comment", value_name = "COMMENT")]
+        comment: Option<String>,
+
+        /// Ask for the comment when logging, offering --comment as default
+        #[clap(long, default_value_t = false)]
+        ask_comment: bool,
+    },
+
+    /// (Alias: rm)
+    /// Remove a template
+    #[clap(alias = "rm")]
+    Remove {
+        /// Template name (eg. retro)
+        name: String,
+    },
+
+    /// (Alias: ls)
+    /// List templates
+    #[clap(alias = "ls")]
+    List,
+}
+
+#[derive(Subcommand)]
+pub enum RecurCommands {
+    /// Log occurrences of the month that are not logged yet
+    /// Example: jtime recur apply --month 2
+    /// Holidays and days off are skipped
+    Apply {
+        /// Month number (1-12)
+        /// Default is current month, up to today
+        #[clap(short, long)]
+        month: Option<Option<u32>>,
 
-        /// Show weekends
+        /// Year of the month
+        /// Default is the current year, or the previous one for later months
         #[clap(long)]
-        show_weekends: Option<bool>,
+        year: Option<i32>,
+
+        /// Skip confirmation
+        #[clap(long, default_value_t = false)]
+        yes: bool,
     },
 
-    /// (Alias: u)
-    /// Update JTime to the latest version
-    /// Example: jtime update
-    #[clap(alias = "u")]
-    Update,
+    /// Add a recurring worklog
+    /// Example: jtime recur add TD-1 1h --on fri --every-weeks 2 --comment retro
+    Add {
+        /// Task identifier (eg. XX-1234)
+        task: String,
+
+        /// Time spent (e.g. 15m)
+        time: String,
+
+        /// Comment for worklog (e.g. standup)
+        #[arg(short = 'c', long = "comment", value_name = "COMMENT")]
+        comment: Option<String>,
+
+        /// `weekdays`, `daily` or a weekday (e.g. fri)
+        #[clap(long, default_value = "weekdays")]
+        on: String,
+
+        /// Repeat every N weeks, counted from the week of --start
+        #[clap(long, value_name = "N")]
+        every_weeks: Option<u32>,
+
+        /// First day the rule applies (eg. 7 or 07-03-2025)
+        #[clap(long, value_name = "DAY", allow_negative_numbers = true)]
+        start: Option<String>,
+    },
+
+    /// (Alias: rm)
+    /// Remove a recurring worklog by its number in `jtime recur list`
+    #[clap(alias = "rm")]
+    Remove {
+        /// Rule number
+        index: usize,
+    },
+
+    /// (Alias: ls)
+    /// List recurring worklogs
+    #[clap(alias = "ls")]
+    List,
+}
+
+#[derive(Subcommand)]
+pub enum OffCommands {
+    /// Add day(s) off, logged to `absence_task` when configured
+    /// Example: jtime off add 12-15 --kind sick
+    Add {
+        /// Day(s) off (eg. 2 or 2-5)
+        #[arg(allow_negative_numbers = true)]
+        day: String,
+
+        /// Kind of the day off
+        #[clap(long, value_enum, default_value_t = DayOffKind::Vacation)]
+        kind: DayOffKind,
+
+        /// Skip confirmation
+        #[clap(long, default_value_t = false)]
+        yes: bool,
+    },
+
+    /// (Alias: rm)
+    /// Remove day(s) off
+    /// Example: jtime off rm 15
+    #[clap(alias = "rm")]
+    Remove {
+        /// Day(s) off (eg. 2 or 2-5)
+        #[arg(allow_negative_numbers = true)]
+        day: String,
+    },
+
+    /// (Alias: ls)
+    /// List days off
+    #[clap(alias = "ls")]
+    List,
+}
+
+/// Output format of `jtime export`.
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Md,
+}
+
+/// What `jtime report` adds worklogs up by.
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum GroupBy {
+    Issue,
+    Project,
+    Epic,
+    Day,
+}
+
+#[derive(Args)]
+pub struct MonthArgs {
+    /// Use cached data
+    #[clap(long, default_value_t = false)]
+    pub cache: bool,
+
+    /// Month number (1-12)
+    /// Default is current month
+    /// Example: 2
+    #[clap(short, long, conflicts_with_all = ["offset", "date"])]
+    pub month: Option<u32>,
+
+    /// Year of the month
+    /// Default is current year
+    #[clap(long, conflicts_with_all = ["offset", "date"])]
+    pub year: Option<i32>,
+
+    /// Months relative to the current one (e.g. -3)
+    #[clap(long, allow_negative_numbers = true, conflicts_with = "date")]
+    pub offset: Option<i32>,
+
+    /// Month containing this day (e.g. 2025-03-14 or last fri)
+    #[clap(long, allow_negative_numbers = true)]
+    pub date: Option<String>,
+}
+
+#[derive(Args)]
+pub struct WeekArgs {
+    /// Use cached data
+    #[clap(long, short, default_value_t = false)]
+    pub cache: bool,
+
+    /// Previous week
+    #[clap(long, short, default_value_t = false, conflicts_with_all = ["offset", "date", "week"])]
+    pub prev: bool,
+
+    /// Weeks relative to the current one (e.g. -3)
+    #[clap(long, allow_negative_numbers = true, conflicts_with_all = ["date", "week"])]
+    pub offset: Option<i32>,
+
+    /// Week containing this day (e.g. 2025-03-14 or last fri)
+    #[clap(long, conflicts_with = "week", allow_negative_numbers = true)]
+    pub date: Option<String>,
+
+    /// ISO week (e.g. 2025-W11, or W11 in the current year)
+    #[clap(long)]
+    pub week: Option<String>,
+}
+
+#[derive(Args, Clone)]
+pub struct LogArgs {
+    /// Task identifier (eg. XX-1234) or template (eg. @retro)
+    /// Several tasks split the time: XX-1,XX-2 evenly, or XX-1:4h,XX-2:50%
+    pub task: Option<String>,
+
+    /// Day in this monh (eg. 2 or multiple days 2-5)
+    /// Also: yesterday, -2, last fri, 2,4,9, last week, 28-01-2025..03-02-2025
+    /// Default is current day
+    /// Example: 2
+    #[arg(allow_negative_numbers = true)]
+    pub day: Option<String>,
+
+    /// Time spent (e.g. 1h30m)
+    /// Default is the template's time, otherwise 8h
+    pub time: Option<String>,
+
+    /// Comment for worklog (e.g. retro)
+    pub comment: Option<String>,
+
+    /// Words after the comment slot, when allocations are separate words
+    #[arg(hide = true, allow_negative_numbers = true)]
+    pub extra: Vec<String>,
+
+    /// Skip confirmation
+    #[clap(long, default_value_t = false)]
+    pub yes: bool,
+
+    /// Task but can be provided as option
+    #[arg(
+        short = 'd',
+        long = "day",
+        value_name = "DAY",
+        allow_negative_numbers = true
+    )]
+    pub option_day: Option<String>,
+
+    /// Task but can be provided as option
+    #[arg(short = 't', long = "time", value_name = "TIME")]
+    pub option_time: Option<String>,
+
+    /// Comment but can be provided as option
+    #[arg(short = 'c', long = "comment", value_name = "COMMENT")]
+    pub option_comment: Option<String>,
+
+    /// Month of the day numbers (1-12)
+    /// Default is current month
+    #[clap(short, long, conflicts_with = "prev_month")]
+    pub month: Option<u32>,
+
+    /// Year of the day numbers
+    /// Default is current year
+    #[clap(long, conflicts_with = "prev_month")]
+    pub year: Option<i32>,
+
+    /// Day numbers are in the previous month
+    #[clap(long, default_value_t = false)]
+    pub prev_month: bool,
+}
+
+impl LogArgs {
+    /// Allocations can also be separate words (`XX-1:4h XX-2:3h 2-5`), which
+    /// land in the day, time, comment and extra slots. Moves them back into
+    /// the task.
+    pub fn with_allocations(&self) -> anyhow::Result<LogArgs> {
+        let mut args = self.clone();
+        let mut words = [args.day.take(), args.time.take(), args.comment.take()]
+            .into_iter()
+            .flatten()
+            .chain(std::mem::take(&mut args.extra))
+            .peekable();
+        if let Some(task) = args.task.as_mut().filter(|t| t.contains(':')) {
+            while let Some(word) = words.next_if(|w| w.contains(':') && !w.contains(' ')) {
+                task.push(',');
+                task.push_str(&word);
+            }
+        }
+        args.day = words.next();
+        args.time = words.next();
+        args.comment = words.next();
+        if let Some(word) = words.next() {
+            anyhow::bail!("Unexpected argument '{}'", word);
+        }
+        Ok(args)
+    }
+
+    pub fn time(&self) -> Option<&str> {
+        self.option_time.as_deref().or(self.time.as_deref())
+    }
+
+    pub fn day(&self) -> Option<String> {
+        self.option_day.clone().or_else(|| self.day.clone())
+    }
+
+    pub fn comment(&self) -> Option<String> {
+        self.option_comment.clone().or_else(|| self.comment.clone())
+    }
+
+    /// First day of the month given with `--month`, `--year` or `--prev-month`.
+    pub fn reference_month(&self) -> anyhow::Result<Option<NaiveDate>> {
+        let today = Utc::now().date_naive().with_day(1).expect("valid day");
+        if self.prev_month {
+            return Ok(Some(today - Months::new(1)));
+        }
+        if self.month.is_none() && self.year.is_none() {
+            return Ok(None);
+        }
+        let (year, month) = (
+            self.year.unwrap_or(today.year()),
+            self.month.unwrap_or(today.month()),
+        );
+        NaiveDate::from_ymd_opt(year, month, 1)
+            .map(Some)
+            .ok_or_else(|| anyhow::anyhow!("Invalid month {} of {}", month, year))
+    }
+}
+
+#[derive(Args)]
+pub struct ConfigArgs {
+    /// Jira URL
+    #[clap(long)]
+    pub url: Option<String>,
+
+    /// Jira token (API token on Jira Cloud)
+    #[clap(long)]
+    pub token: Option<String>,
+
+    /// Jira Cloud (email + API token) instead of Server / Data Center
+    #[clap(long)]
+    pub cloud: Option<bool>,
+
+    /// Atlassian account email, required on Jira Cloud
+    #[clap(long)]
+    pub email: Option<String>,
+
+    /// Nager url
+    #[clap(long)]
+    pub nager_url: Option<Option<String>>,
+
+    /// Country code for nager
+    #[clap(long)]
+    pub nager_country_code: Option<Option<String>>,
+
+    /// Show weekends
+    #[clap(long)]
+    pub show_weekends: Option<bool>,
+
+    /// Where worklogs are read from
+    #[clap(long)]
+    pub worklog_backend: Option<WorklogBackendKind>,
+
+    /// JQL selecting tasks offered in the picker (per profile with --profile)
+    #[clap(long)]
+    pub task_jql: Option<Option<String>>,
+
+    /// Weeks of worklogs used to rank the task picker
+    #[clap(long)]
+    pub recent_weeks: Option<u32>,
+
+    /// Round timer durations to this unit (e.g. 15m)
+    #[clap(long)]
+    pub timer_rounding: Option<Option<String>>,
+
+    /// Time expected to be logged per working day (e.g. 8h)
+    #[clap(long)]
+    pub daily_target: Option<Option<String>>,
+
+    /// Warn before a day's total goes over this (e.g. 10h)
+    #[clap(long)]
+    pub daily_cap: Option<Option<String>>,
+
+    /// Length of a day in durations like 2d (e.g. 7h30m)
+    /// Default is Jira's time tracking setting
+    #[clap(long)]
+    pub workday: Option<Option<String>>,
+
+    /// Expected time on a weekday (e.g. fri=4h), repeatable
+    /// Empty value restores `daily_target`
+    #[clap(long, value_name = "WEEKDAY=TIME")]
+    pub expected_hours: Vec<String>,
+
+    /// Default task used by `jtime fill`
+    #[clap(long)]
+    pub fill_task: Option<Option<String>>,
+
+    /// Task that days off are logged to
+    #[clap(long)]
+    pub absence_task: Option<Option<String>>,
+
+    /// Tempo API url
+    #[clap(long)]
+    pub tempo_url: Option<Option<String>>,
+
+    /// Tempo API token
+    #[clap(long)]
+    pub tempo_token: Option<String>,
+
+    /// Tempo work attribute sent with every worklog (e.g. _Account_=ACME)
+    /// Empty value removes the attribute
+    #[clap(long, value_name = "KEY=VALUE")]
+    pub tempo_attribute: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_accepts_negative_day_offsets() {
+        let log_args = |args: &[&str]| match Cli::try_parse_from(args).unwrap().command {
+            Commands::Log(args) => args,
+            _ => panic!("expected log"),
+        };
+
+        let args = log_args(&["jtime", "log", "XX-1", "-2", "1h"]);
+        assert_eq!(args.day().as_deref(), Some("-2"));
+        assert_eq!(args.time(), Some("1h"));
+
+        let args = log_args(&["jtime", "log", "XX-1", "--day", "-2"]);
+        assert_eq!(args.day().as_deref(), Some("-2"));
+        let args = log_args(&["jtime", "log", "XX-1", "-d", "-1"]);
+        assert_eq!(args.day().as_deref(), Some("-1"));
+
+        assert!(Cli::try_parse_from(["jtime", "log", "XX-1", "-x"]).is_err());
+    }
+
+    #[test]
+    fn test_month_and_week_accept_negative_dates() {
+        match Cli::try_parse_from(["jtime", "w", "--date", "-7"])
+            .unwrap()
+            .command
+        {
+            Commands::Week(args) => assert_eq!(args.date.as_deref(), Some("-7")),
+            _ => panic!("expected week"),
+        }
+        match Cli::try_parse_from(["jtime", "m", "--date", "-7"])
+            .unwrap()
+            .command
+        {
+            Commands::Month(args) => assert_eq!(args.date.as_deref(), Some("-7")),
+            _ => panic!("expected month"),
+        }
+    }
+
+    #[test]
+    fn test_log_allocations_as_separate_words() -> anyhow::Result<()> {
+        let log_args = |args: &[&str]| match Cli::try_parse_from(args).unwrap().command {
+            Commands::Log(args) => args.with_allocations(),
+            _ => panic!("expected log"),
+        };
+
+        let args = log_args(&[
+            "jtime", "log", "XX-1:1h", "XX-2:1h", "XX-3:1h", "XX-4:1h", "XX-5:1h", "-2",
+        ])?;
+        assert_eq!(
+            args.task.as_deref(),
+            Some("XX-1:1h,XX-2:1h,XX-3:1h,XX-4:1h,XX-5:1h")
+        );
+        assert_eq!(args.day().as_deref(), Some("-2"));
+
+        let args = log_args(&["jtime", "log", "XX-1", "2", "1h", "retro"])?;
+        assert_eq!(args.task.as_deref(), Some("XX-1"));
+        assert_eq!(args.comment().as_deref(), Some("retro"));
+
+        assert!(log_args(&["jtime", "log", "XX-1", "2", "1h", "retro", "more"]).is_err());
+        Ok(())
+    }
 }
@@ -1,16 +1,10 @@
+use crate::cli::ConfigArgs;
 use crate::config::Config;
 use anyhow::Result;
 use colored::Colorize;
 
-pub fn execute(
-    mut config: Config,
-    url: &Option<String>,
-    token: &Option<String>,
-    nager_url: &Option<Option<String>>,
-    nager_country_code: &Option<Option<String>>,
-    show_weekends: &Option<bool>,
-) -> Result<()> {
-    if let Some(raw_url) = url {
+pub fn execute(mut config: Config, args: &ConfigArgs, profile: &Option<String>) -> Result<()> {
+    if let Some(raw_url) = &args.url {
         let clean_url = raw_url
             .trim_end_matches('/')
             .trim_start_matches("https://")
@@ -18,24 +12,111 @@ pub fn execute(
         config.jira_url = clean_url.to_string();
     }
 
-    if let Some(token) = token {
+    if let Some(token) = &args.token {
         config.jira_token = token.clone();
     }
-    if let Some(nager_url) = nager_url {
+    if let Some(cloud) = &args.cloud {
+        config.jira_cloud = *cloud;
+    }
+    if let Some(email) = &args.email {
+        config.jira_email = Some(email.clone());
+    }
+    if let Some(nager_url) = &args.nager_url {
         config.nager_url = nager_url.clone();
     }
-    if let Some(nager_country_code) = nager_country_code {
+    if let Some(nager_country_code) = &args.nager_country_code {
         config.nager_country_code = nager_country_code.clone();
     }
-    if let Some(show_weekends) = show_weekends {
+    if let Some(show_weekends) = &args.show_weekends {
         config.show_weekends = *show_weekends;
     }
+    if let Some(worklog_backend) = &args.worklog_backend {
+        config.worklog_backend = *worklog_backend;
+    }
+    if let Some(tempo_url) = &args.tempo_url {
+        config.tempo_url = tempo_url.clone();
+    }
+    if let Some(tempo_token) = &args.tempo_token {
+        config.tempo_token = Some(tempo_token.clone());
+    }
+    if let Some(task_jql) = &args.task_jql {
+        match profile {
+            Some(name) => {
+                config.profiles.entry(name.clone()).or_default().task_jql = task_jql.clone()
+            }
+            None => config.task_jql = task_jql.clone(),
+        }
+    }
+    if let Some(recent_weeks) = &args.recent_weeks {
+        config.recent_weeks = Some(*recent_weeks);
+    }
+    if let Some(timer_rounding) = &args.timer_rounding {
+        config.timer_rounding = timer_rounding.clone();
+        config.timer_rounding()?;
+    }
+    if let Some(daily_target) = &args.daily_target {
+        config.daily_target = daily_target.clone();
+    }
+    if let Some(daily_cap) = &args.daily_cap {
+        config.daily_cap = daily_cap.clone();
+        config.daily_cap()?;
+    }
+    if let Some(workday) = &args.workday {
+        config.workday = workday.clone();
+        config.workday()?;
+    }
+    if let Some(fill_task) = &args.fill_task {
+        config.fill_task = fill_task.clone();
+    }
+    if let Some(absence_task) = &args.absence_task {
+        config.absence_task = absence_task.clone();
+    }
+    for expected in &args.expected_hours {
+        let (weekday, time) = expected
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("Invalid value {}, use WEEKDAY=TIME", expected))?;
+        if time.is_empty() {
+            config.expected_hours.remove(weekday);
+        } else {
+            config
+                .expected_hours
+                .insert(weekday.to_string(), time.to_string());
+        }
+    }
+    config.schedule()?;
+    for attribute in &args.tempo_attribute {
+        let (key, value) = attribute
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("Invalid attribute {}, use KEY=VALUE", attribute))?;
+        if value.is_empty() {
+            config.tempo_attributes.remove(key);
+        } else {
+            config
+                .tempo_attributes
+                .insert(key.to_string(), value.to_string());
+        }
+    }
 
-    if url.is_some()
-        || token.is_some()
-        || nager_url.is_some()
-        || nager_country_code.is_some()
-        || show_weekends.is_some()
+    if args.url.is_some()
+        || args.token.is_some()
+        || args.cloud.is_some()
+        || args.email.is_some()
+        || args.nager_url.is_some()
+        || args.nager_country_code.is_some()
+        || args.show_weekends.is_some()
+        || args.worklog_backend.is_some()
+        || args.tempo_url.is_some()
+        || args.tempo_token.is_some()
+        || !args.tempo_attribute.is_empty()
+        || args.task_jql.is_some()
+        || args.recent_weeks.is_some()
+        || args.timer_rounding.is_some()
+        || args.daily_target.is_some()
+        || args.fill_task.is_some()
+        || args.daily_cap.is_some()
+        || args.workday.is_some()
+        || !args.expected_hours.is_empty()
+        || args.absence_task.is_some()
     {
         config.save()?;
         println!("{}", "Configuration updated successfully! :)".green());
@@ -44,10 +125,22 @@ pub fn execute(
 
     println!("Jira URL (url): {}", config.jira_url.green());
     println!("Jira token (token): {}", config.jira_token.green());
-    if let Some(nager_url) = config.nager_url {
+    println!(
+        "Jira Cloud (cloud): {}",
+        config.jira_cloud.to_string().green()
+    );
+    match &config.jira_email {
+        Some(email) => println!("Jira email (email): {}", email.green()),
+        None if config.jira_cloud => println!(
+            "{}",
+            "Jira Cloud requires an email: jtime config --email you@example.com".yellow()
+        ),
+        None => {}
+    }
+    if let Some(nager_url) = &config.nager_url {
         println!("Nager URL (nager_url): {}", nager_url.green());
     }
-    if let Some(nager_country_code) = config.nager_country_code {
+    if let Some(nager_country_code) = &config.nager_country_code {
         println!(
             "Nager Country Code (nager_country_code): {}",
             nager_country_code.green()
@@ -57,6 +150,65 @@ pub fn execute(
         "Show weekends (show_weekends): {}",
         config.show_weekends.to_string().green()
     );
+    println!(
+        "Worklog backend (worklog_backend): {}",
+        config.worklog_backend.to_string().green()
+    );
+    if let Some(tempo_url) = &config.tempo_url {
+        println!("Tempo URL (tempo_url): {}", tempo_url.green());
+    }
+    if let Some(tempo_token) = &config.tempo_token {
+        println!("Tempo token (tempo_token): {}", tempo_token.green());
+    }
+    for (key, value) in &config.tempo_attributes {
+        println!(
+            "Tempo attribute (tempo_attribute): {}",
+            format!("{}={}", key, value).green()
+        );
+    }
+    if let Some(task_jql) = &config.task_jql {
+        println!("Task JQL (task_jql): {}", task_jql.green());
+    }
+    println!(
+        "Recent weeks (recent_weeks): {}",
+        config.recent_weeks().to_string().green()
+    );
+    if let Some(timer_rounding) = &config.timer_rounding {
+        println!(
+            "Timer rounding (timer_rounding): {}",
+            timer_rounding.green()
+        );
+    }
+    if let Some(daily_target) = &config.daily_target {
+        println!("Daily target (daily_target): {}", daily_target.green());
+    }
+    if let Some(daily_cap) = &config.daily_cap {
+        println!("Daily cap (daily_cap): {}", daily_cap.green());
+    }
+    if let Some(workday) = &config.workday {
+        println!("Workday (workday): {}", workday.green());
+    }
+    for (weekday, time) in &config.expected_hours {
+        println!(
+            "Expected hours (expected_hours): {}",
+            format!("{}={}", weekday, time).green()
+        );
+    }
+    if let Some(fill_task) = &config.fill_task {
+        println!("Fill task (fill_task): {}", fill_task.green());
+    }
+    if let Some(absence_task) = &config.absence_task {
+        println!("Absence task (absence_task): {}", absence_task.green());
+    }
+    for (name, profile) in &config.profiles {
+        if let Some(task_jql) = &profile.task_jql {
+            println!(
+                "Task JQL (--profile {} task_jql): {}",
+                name.blue(),
+                task_jql.green()
+            );
+        }
+    }
     println!(
         "{} {}",
         "You can change the values:".yellow(),
@@ -0,0 +1,39 @@
+use crate::api::Jira;
+use crate::commands::prompt::{confirm, select_worklog};
+use crate::duration::format_time;
+use anyhow::{Context, Result};
+use colored::Colorize;
+
+pub fn execute(api: &Jira, cli_day: &Option<String>, yes: &bool) -> Result<()> {
+    let worklog = match select_worklog(api, cli_day, "delete")? {
+        Some(w) => w,
+        None => {
+            println!("Aborted.");
+            return Ok(());
+        }
+    };
+
+    println!(
+        "Deleting {} logged on {} for task {}",
+        format_time(worklog.time_spent_seconds).red(),
+        worklog.day.format("%Y-%m-%d").to_string().red(),
+        worklog.task.red()
+    );
+
+    if !confirm(yes)? {
+        println!("Aborted.");
+        return Ok(());
+    }
+
+    api.delete_worklog(
+        worklog.issue(),
+        &worklog.id,
+        worklog.day.date_naive(),
+        worklog.time_spent_seconds,
+    )
+    .context(format!("Failed to delete worklog for {}", worklog.task))?;
+
+    println!("{}", "Worklog deleted successfully!".green());
+
+    Ok(())
+}
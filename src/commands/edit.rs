@@ -0,0 +1,91 @@
+use crate::api::Jira;
+use crate::commands::log::single_date;
+use crate::commands::prompt::{confirm, select_worklog};
+use crate::duration::{format_time, parse_duration};
+use anyhow::{Context, Result};
+use colored::Colorize;
+use dialoguer::{theme::ColorfulTheme, Input};
+
+pub fn execute(
+    api: &Jira,
+    cli_day: &Option<String>,
+    cli_time: &Option<String>,
+    cli_date: &Option<String>,
+    cli_comment: &Option<String>,
+    yes: &bool,
+) -> Result<()> {
+    let worklog = match select_worklog(api, cli_day, "edit")? {
+        Some(w) => w,
+        None => {
+            println!("Aborted.");
+            return Ok(());
+        }
+    };
+
+    let ask = cli_time.is_none() && cli_date.is_none() && cli_comment.is_none();
+
+    // Jira may not report the duration; keeping "0m" would wipe the worklog
+    if cli_time.is_none() && worklog.time_spent_seconds == 0 {
+        anyhow::bail!(
+            "Time spent of the worklog on {} is unknown, pass it with --time",
+            worklog.task
+        );
+    }
+
+    let time = match cli_time.clone() {
+        Some(t) => t,
+        None if ask => Input::with_theme(&ColorfulTheme::default())
+            .with_prompt("Time spent:")
+            .default(format_time(worklog.time_spent_seconds))
+            .interact_text()?,
+        None => format_time(worklog.time_spent_seconds),
+    };
+
+    let date = match cli_date.clone() {
+        Some(d) => d,
+        None if ask => Input::with_theme(&ColorfulTheme::default())
+            .with_prompt("Day:")
+            .default(worklog.day.format("%d-%m-%Y").to_string())
+            .interact_text()?,
+        None => worklog.day.format("%d-%m-%Y").to_string(),
+    };
+
+    let comment = match cli_comment.clone() {
+        Some(c) => Some(c),
+        None if ask => Some(
+            Input::with_theme(&ColorfulTheme::default())
+                .with_prompt("Comment:")
+                .default(worklog.comment.clone().unwrap_or_default())
+                .allow_empty(true)
+                .interact_text()?,
+        ),
+        None => worklog.comment.clone(),
+    }
+    .filter(|c| !c.is_empty());
+
+    let time_spent = parse_duration(&time, || api.work_units())?;
+    let date = single_date(&date)?;
+
+    println!(
+        "Updating worklog of {} to {} on {}{}",
+        worklog.task.green(),
+        format_time(time_spent).green(),
+        date.format("%Y-%m-%d").to_string().green(),
+        comment
+            .as_ref()
+            .map(|c| format!(" ({})", c.green()))
+            .unwrap_or_default()
+    );
+
+    if !confirm(yes)? {
+        println!("Aborted.");
+        return Ok(());
+    }
+
+    api.update_worklog(&worklog, time_spent, &date, comment)
+        .context(format!("Failed to update worklog for {}", worklog.task))?;
+
+    println!("{}", "Worklog updated successfully!".green());
+
+    Ok(())
+}
@@ -0,0 +1,339 @@
+use crate::api::Jira;
+use crate::cli::ExportFormat;
+use crate::commands::log::date_range;
+use crate::duration::format_time;
+use crate::models::{Task, WorkLogList};
+use anyhow::Result;
+use chrono::{Local, NaiveDate, TimeZone};
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Export {
+    pub worklogs: Vec<ExportRow>,
+    pub issues: Vec<IssueTotal>,
+    pub days: Vec<DayTotal>,
+    pub seconds: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ExportRow {
+    pub issue: String,
+    pub summary: String,
+    pub date: String,
+    pub started: String,
+    pub seconds: u64,
+    pub comment: String,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct IssueTotal {
+    pub issue: String,
+    pub summary: String,
+    pub seconds: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct DayTotal {
+    pub date: String,
+    pub seconds: u64,
+}
+
+pub fn execute(
+    api: &Jira,
+    from: &Option<String>,
+    to: &Option<String>,
+    format: &ExportFormat,
+    output: &Option<String>,
+) -> Result<()> {
+    let range = date_range(from, to)?;
+    let worklogs = api.fetch_worklogs(range)?;
+    let mut keys: Vec<String> = worklogs.iter().map(|w| w.task.clone()).collect();
+    keys.sort();
+    keys.dedup();
+    let tasks = if keys.is_empty() {
+        Vec::new()
+    } else {
+        api.get_tasks(&keys)?
+    };
+
+    let export = build(&worklogs, &tasks, &Local);
+    let content = match format {
+        ExportFormat::Csv => to_csv(&export)?,
+        ExportFormat::Json => serde_json::to_string_pretty(&export)? + "\n",
+        ExportFormat::Md => to_markdown(&export),
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, content)?;
+            println!(
+                "Exported {} worklog(s), {} in total, to {}",
+                export.worklogs.len().to_string().green(),
+                format_time(export.seconds).green(),
+                path.green()
+            );
+        }
+        None => print!("{}", content),
+    }
+
+    Ok(())
+}
+
+/// Worklogs in date order with per-issue and per-day totals, with dates and
+/// start times in the `tz` time zone.
+pub fn build<Tz: TimeZone>(worklogs: &WorkLogList, tasks: &[Task], tz: &Tz) -> Export
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let summary = |key: &str| {
+        tasks
+            .iter()
+            .find(|t| t.id == key)
+            .map(|t| t.name.clone())
+            .unwrap_or_default()
+    };
+
+    let mut sorted: Vec<_> = worklogs.iter().collect();
+    sorted.sort_by_key(|w| w.day);
+
+    let mut issues: BTreeMap<&str, u64> = BTreeMap::new();
+    let mut days: BTreeMap<NaiveDate, u64> = BTreeMap::new();
+    for worklog in &sorted {
+        *issues.entry(&worklog.task).or_default() += worklog.time_spent_seconds;
+        *days
+            .entry(worklog.day.with_timezone(tz).date_naive())
+            .or_default() += worklog.time_spent_seconds;
+    }
+
+    Export {
+        worklogs: sorted
+            .iter()
+            .map(|w| ExportRow {
+                issue: w.task.clone(),
+                summary: summary(&w.task),
+                date: w.day.with_timezone(tz).format("%Y-%m-%d").to_string(),
+                started: w.day.with_timezone(tz).format("%H:%M").to_string(),
+                seconds: w.time_spent_seconds,
+                comment: w.comment.clone().unwrap_or_default(),
+            })
+            .collect(),
+        issues: issues
+            .iter()
+            .map(|(issue, seconds)| IssueTotal {
+                issue: issue.to_string(),
+                summary: summary(issue),
+                seconds: *seconds,
+            })
+            .collect(),
+        days: days
+            .iter()
+            .map(|(date, seconds)| DayTotal {
+                date: date.format("%Y-%m-%d").to_string(),
+                seconds: *seconds,
+            })
+            .collect(),
+        seconds: worklogs.iter().map(|w| w.time_spent_seconds).sum(),
+    }
+}
+
+/// One row per worklog, followed by the per-issue, per-day and overall
+/// totals; the `kind` column tells them apart.
+fn to_csv(export: &Export) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record([
+        "kind", "issue", "summary", "date", "started", "seconds", "comment",
+    ])?;
+    for row in &export.worklogs {
+        writer.write_record([
+            "worklog",
+            &row.issue,
+            &row.summary,
+            &row.date,
+            &row.started,
+            &row.seconds.to_string(),
+            &row.comment,
+        ])?;
+    }
+    for total in &export.issues {
+        writer.write_record([
+            "issue",
+            &total.issue,
+            &total.summary,
+            "",
+            "",
+            &total.seconds.to_string(),
+            "",
+        ])?;
+    }
+    for total in &export.days {
+        writer.write_record([
+            "day",
+            "",
+            "",
+            &total.date,
+            "",
+            &total.seconds.to_string(),
+            "",
+        ])?;
+    }
+    writer.write_record(["total", "", "", "", "", &export.seconds.to_string(), ""])?;
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+fn to_markdown(export: &Export) -> String {
+    let cell = |text: &str| text.replace('|', "\\|").replace('\n', " ");
+    let mut lines = vec![
+        "## Worklogs".to_string(),
+        String::new(),
+        "| Issue | Summary | Date | Started | Time | Comment |".to_string(),
+        "|---|---|---|---|---:|---|".to_string(),
+    ];
+    lines.extend(export.worklogs.iter().map(|row| {
+        format!(
+            "| {} | {} | {} | {} | {} | {} |",
+            row.issue,
+            cell(&row.summary),
+            row.date,
+            row.started,
+            format_time(row.seconds),
+            cell(&row.comment)
+        )
+    }));
+
+    lines.extend([
+        String::new(),
+        "## Per issue".to_string(),
+        String::new(),
+        "| Issue | Summary | Time |".to_string(),
+        "|---|---|---:|".to_string(),
+    ]);
+    lines.extend(export.issues.iter().map(|total| {
+        format!(
+            "| {} | {} | {} |",
+            total.issue,
+            cell(&total.summary),
+            format_time(total.seconds)
+        )
+    }));
+
+    lines.extend([
+        String::new(),
+        "## Per day".to_string(),
+        String::new(),
+        "| Date | Time |".to_string(),
+        "|---|---:|".to_string(),
+    ]);
+    lines.extend(
+        export
+            .days
+            .iter()
+            .map(|total| format!("| {} | {} |", total.date, format_time(total.seconds))),
+    );
+    lines.push(format!(
+        "| **Total** | **{}** |",
+        format_time(export.seconds)
+    ));
+
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{worklog, WorkLog};
+    use chrono::Utc;
+
+    fn export() -> Export {
+        let worklogs = vec![
+            worklog(4, "XX-2", 3600),
+            WorkLog {
+                comment: Some("review | fix".to_string()),
+                ..worklog(3, "XX-1", 5400)
+            },
+            worklog(3, "XX-2", 1800),
+        ];
+        let tasks = vec![Task {
+            id: "XX-1".to_string(),
+            name: "Login, SSO".to_string(),
+            status: None,
+            project: None,
+        }];
+        build(&worklogs, &tasks, &Utc)
+    }
+
+    #[test]
+    fn test_build_totals() {
+        let export = export();
+
+        assert_eq!(export.worklogs[0].issue, "XX-1");
+        assert_eq!(export.worklogs[0].summary, "Login, SSO");
+        assert_eq!(export.worklogs[0].started, "08:00");
+        assert_eq!(export.worklogs[2].date, "2025-03-04");
+        assert_eq!(
+            export.issues,
+            vec![
+                IssueTotal {
+                    issue: "XX-1".to_string(),
+                    summary: "Login, SSO".to_string(),
+                    seconds: 5400,
+                },
+                IssueTotal {
+                    issue: "XX-2".to_string(),
+                    summary: String::new(),
+                    seconds: 5400,
+                },
+            ]
+        );
+        assert_eq!(
+            export.days,
+            vec![
+                DayTotal {
+                    date: "2025-03-03".to_string(),
+                    seconds: 7200,
+                },
+                DayTotal {
+                    date: "2025-03-04".to_string(),
+                    seconds: 3600,
+                },
+            ]
+        );
+        assert_eq!(export.seconds, 10800);
+
+        let late = WorkLog {
+            day: NaiveDate::from_ymd_opt(2025, 3, 3)
+                .unwrap()
+                .and_hms_opt(23, 30, 0)
+                .unwrap()
+                .and_utc(),
+            task: "XX-1".to_string(),
+            time_spent_seconds: 1800,
+            ..Default::default()
+        };
+        let tz = chrono::FixedOffset::east_opt(2 * 3600).unwrap();
+        let export = build(&vec![late], &[], &tz);
+        assert_eq!(export.worklogs[0].date, "2025-03-04");
+        assert_eq!(export.worklogs[0].started, "01:30");
+        assert_eq!(export.days[0].date, "2025-03-04");
+    }
+
+    #[test]
+    fn test_csv_and_markdown_escaping() -> Result<()> {
+        let export = export();
+
+        let csv = to_csv(&export)?;
+        assert!(csv.starts_with("kind,issue,summary,date,started,seconds,comment\n"));
+        assert!(csv.contains("worklog,XX-1,\"Login, SSO\",2025-03-03,08:00,5400,review | fix\n"));
+        assert!(csv.contains("worklog,XX-2,,2025-03-04,08:00,3600,\n"));
+        assert!(csv.contains("issue,XX-1,\"Login, SSO\",,,5400,\n"));
+        assert!(csv.contains("day,,,2025-03-03,,7200,\n"));
+        assert!(csv.ends_with("total,,,,,10800,\n"));
+        assert_eq!(csv.lines().count(), 9);
+
+        let md = to_markdown(&export);
+        assert!(md.contains("| XX-1 | Login, SSO | 2025-03-03 | 08:00 | 1h30m | review \\| fix |"));
+        assert!(md.ends_with("| **Total** | **3h** |\n"));
+        Ok(())
+    }
+}
@@ -0,0 +1,242 @@
+use crate::api::nager::HolidayMap;
+use crate::api::{Jira, Nager};
+use crate::commands::log::parse_date;
+use crate::commands::prompt::confirm;
+use crate::config::Config;
+use crate::duration::{format_time, parse_duration, split_evenly};
+use crate::models::{DateRange, WorkLogList, WorkLogListExt};
+use crate::state::days_off::DaysOff;
+use crate::state::journal::Operation;
+use anyhow::{Context, Result};
+use chrono::{Datelike, NaiveDate};
+use cli_table::{format::Justify, Cell, Style, Table};
+use colored::Colorize;
+use dialoguer::{theme::ColorfulTheme, MultiSelect};
+
+/// A worklog proposed to close the gap of a day.
+#[derive(Debug, PartialEq)]
+pub struct FillEntry {
+    pub date: NaiveDate,
+    pub logged: u64,
+    pub missing: u64,
+    pub task: String,
+    pub time_spent: u64,
+}
+
+pub fn execute(
+    config: &Config,
+    api: &Jira,
+    nager: &Nager,
+    day: &str,
+    cli_tasks: &[String],
+    cli_target: &Option<String>,
+    yes: &bool,
+) -> Result<()> {
+    let cli_target = cli_target
+        .as_deref()
+        .map(|target| parse_duration(target, || api.work_units()))
+        .transpose()?;
+    let schedule = config.schedule()?;
+
+    let dates: Vec<NaiveDate> = parse_date(day, false)?
+        .into_iter()
+        .filter(|d| d.weekday().num_days_from_monday() < 5)
+        .collect();
+    let (Some(&from), Some(&to)) = (dates.iter().min(), dates.iter().max()) else {
+        println!("Nothing to fill");
+        return Ok(());
+    };
+    let range = DateRange { from, to };
+
+    let holidays = match nager.get_holidays_map_for_range(&range) {
+        Ok(holidays) => holidays,
+        Err(err) => {
+            println!("{} {}", "Can't check holidays:".yellow(), err);
+            HolidayMap::new()
+        }
+    };
+    let days_off = DaysOff::load()?;
+    let dates: Vec<NaiveDate> = dates
+        .into_iter()
+        .filter(|d| !holidays.contains_key(&d.format("%Y-%m-%d").to_string()))
+        .filter(|d| days_off.get(*d).is_none())
+        .collect();
+
+    let worklogs = api.fetch_worklogs(range)?;
+
+    let tasks = if !cli_tasks.is_empty() {
+        cli_tasks.to_vec()
+    } else if let Some(task) = &config.fill_task {
+        vec![task.clone()]
+    } else {
+        let actually_works = api.actually_works()?;
+        let selection = MultiSelect::with_theme(&ColorfulTheme::default())
+            .with_prompt("Please select the task(s) to fill the missing time with:")
+            .items(&actually_works.iter().map(|t| t.label()).collect::<Vec<_>>())
+            .interact()?;
+        selection
+            .into_iter()
+            .map(|i| actually_works[i].id.clone())
+            .collect()
+    };
+    if tasks.is_empty() {
+        println!("Aborted.");
+        return Ok(());
+    }
+
+    let entries = plan(
+        &dates,
+        &worklogs,
+        |date| cli_target.unwrap_or_else(|| schedule.expected_on(date)),
+        &tasks,
+    );
+    if entries.is_empty() {
+        println!(
+            "{}",
+            "Every day is already logged. Time for coffee! ☕".green()
+        );
+        return Ok(());
+    }
+
+    println!("{}", preview(&entries)?);
+
+    if !confirm(yes)? {
+        println!("Aborted.");
+        return Ok(());
+    }
+
+    let mut operation = Operation::new("fill");
+    let result = entries.iter().try_for_each(|entry| -> Result<()> {
+        let worklog_id = api
+            .log_worktime(&entry.task, entry.time_spent, &entry.date, None)
+            .context(format!(
+                "Failed to log time for {}",
+                entry.date.format("%Y-%m-%d")
+            ))?;
+        operation.add(&entry.task, worklog_id, entry.date, entry.time_spent);
+        Ok(())
+    });
+    operation.record()?;
+    result?;
+
+    println!(
+        "{}",
+        "Filled missing time successfully! Time for coffee! ☕".green()
+    );
+
+    Ok(())
+}
+
+/// Spreads each day's shortfall against its target evenly across `tasks`.
+pub fn plan(
+    dates: &[NaiveDate],
+    worklogs: &WorkLogList,
+    target: impl Fn(NaiveDate) -> u64,
+    tasks: &[String],
+) -> Vec<FillEntry> {
+    dates
+        .iter()
+        .flat_map(|date| {
+            let logged = worklogs.logged_on(*date);
+            let missing = target(*date).saturating_sub(logged);
+            split_evenly(missing, tasks.len())
+                .into_iter()
+                .zip(tasks)
+                .filter(|(time_spent, _)| *time_spent > 0)
+                .map(move |(time_spent, task)| FillEntry {
+                    date: *date,
+                    logged,
+                    missing,
+                    task: task.clone(),
+                    time_spent,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn preview(entries: &[FillEntry]) -> Result<String> {
+    let table = entries
+        .iter()
+        .map(|entry| {
+            vec![
+                entry.date.format("%Y-%m-%d").to_string().cell(),
+                format_time(entry.logged).cell().justify(Justify::Right),
+                format_time(entry.missing)
+                    .red()
+                    .cell()
+                    .justify(Justify::Right),
+                entry.task.green().cell(),
+                format_time(entry.time_spent).cell().justify(Justify::Right),
+            ]
+        })
+        .collect::<Vec<_>>()
+        .table()
+        .title(vec![
+            "Date".cell(),
+            "Logged".cell(),
+            "Missing".cell(),
+            "Task".cell(),
+            "Time".cell(),
+        ])
+        .bold(true);
+
+    Ok(table.display()?.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::worklog;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 3, day).unwrap()
+    }
+
+    #[test]
+    fn test_plan_fills_shortfall() {
+        let worklogs = vec![worklog(3, "XX-1", 6 * 3600), worklog(4, "XX-1", 9 * 3600)];
+        let tasks = vec!["XX-2".to_string(), "XX-3".to_string()];
+
+        let entries = plan(
+            &[date(3), date(4), date(5)],
+            &worklogs,
+            |date| if date.day() == 5 { 4 * 3600 } else { 8 * 3600 },
+            &tasks,
+        );
+
+        assert_eq!(
+            entries,
+            vec![
+                FillEntry {
+                    date: date(3),
+                    logged: 6 * 3600,
+                    missing: 2 * 3600,
+                    task: "XX-2".to_string(),
+                    time_spent: 3600,
+                },
+                FillEntry {
+                    date: date(3),
+                    logged: 6 * 3600,
+                    missing: 2 * 3600,
+                    task: "XX-3".to_string(),
+                    time_spent: 3600,
+                },
+                FillEntry {
+                    date: date(5),
+                    logged: 0,
+                    missing: 4 * 3600,
+                    task: "XX-2".to_string(),
+                    time_spent: 2 * 3600,
+                },
+                FillEntry {
+                    date: date(5),
+                    logged: 0,
+                    missing: 4 * 3600,
+                    task: "XX-3".to_string(),
+                    time_spent: 2 * 3600,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use crate::api::nager::HolidayMap;
+use crate::api::{Jira, Nager};
+use crate::commands::log::single_date;
+use crate::commands::prompt::confirm;
+use crate::config::Config;
+use crate::duration::{format_time, parse_duration, WorkUnits};
+use crate::models::{DateRange, WorkLog, WorkLogState};
+use crate::state::days_off::DaysOff;
+use crate::state::journal::Operation;
+use crate::view::{helper::Helper, Calendar, Render};
+use anyhow::Result;
+use chrono::{Datelike, NaiveDate};
+use colored::Colorize;
+use serde::Deserialize;
+
+/// A row as written in the file, `duration` is accepted for `time`.
+#[derive(Debug, Deserialize)]
+struct ImportRow {
+    date: String,
+    task: String,
+    #[serde(alias = "duration")]
+    time: String,
+    #[serde(default)]
+    comment: Option<String>,
+}
+
+/// A validated row, numbered from 1 in file order.
+#[derive(Debug, PartialEq)]
+pub struct ImportEntry {
+    pub row: usize,
+    pub date: NaiveDate,
+    pub task: String,
+    pub time_spent: u64,
+    pub comment: Option<String>,
+}
+
+pub fn execute(config: &Config, api: &Jira, nager: &Nager, file: &str, yes: &bool) -> Result<()> {
+    let content = std::fs::read_to_string(file)
+        .map_err(|err| anyhow::anyhow!("Failed to read {}: {}", file, err))?;
+    let rows = if file.to_lowercase().ends_with(".json") {
+        parse_json(&content)?
+    } else {
+        parse_csv(&content)?
+    };
+
+    let (entries, errors) = validate(rows, || api.work_units());
+    if !errors.is_empty() {
+        for error in &errors {
+            println!("{} {}", "✗".red(), error);
+        }
+        anyhow::bail!("{} invalid row(s), nothing was imported", errors.len());
+    }
+    let (Some(from), Some(to)) = (
+        entries.iter().map(|e| e.date).min(),
+        entries.iter().map(|e| e.date).max(),
+    ) else {
+        println!("Nothing to import");
+        return Ok(());
+    };
+
+    let range = DateRange {
+        from: Calendar::range_days_for_week(from)?.from,
+        to: Calendar::range_days_for_week(to)?.to,
+    };
+    let holiday_map = match nager.get_holidays_map_for_range(&range) {
+        Ok(holidays) => holidays,
+        Err(err) => {
+            println!("{} {}", "Can't check holidays:".yellow(), err);
+            HolidayMap::new()
+        }
+    };
+    let mut worklogs = api.fetch_worklogs(range.clone())?;
+    worklogs.extend(entries.iter().map(|entry| entry.as_worklog()));
+    let show_weekends = config.show_weekends
+        || entries
+            .iter()
+            .any(|e| e.date.weekday().number_from_monday() > 5);
+    println!(
+        "{}",
+        Calendar::render(
+            range,
+            worklogs,
+            show_weekends,
+            Some(holiday_map),
+            &DaysOff::load()?,
+            &config.schedule()?
+        )?
+    );
+    println!(
+        "Importing {} worklog(s), {} in total",
+        entries.len().to_string().green(),
+        format_time(entries.iter().map(|e| e.time_spent).sum()).green()
+    );
+
+    if !confirm(yes)? {
+        println!("Aborted.");
+        return Ok(());
+    }
+
+    let mut operation = Operation::new("import");
+    let mut failed = 0;
+    for entry in &entries {
+        let summary = format!(
+            "Row {}: {} {} {}",
+            entry.row,
+            entry.date.format("%Y-%m-%d"),
+            entry.task,
+            format_time(entry.time_spent)
+        );
+        match api.log_worktime(
+            &entry.task,
+            entry.time_spent,
+            &entry.date,
+            entry.comment.clone(),
+        ) {
+            Ok(worklog_id) => {
+                operation.add(&entry.task, worklog_id, entry.date, entry.time_spent);
+                println!("{} {}", "✓".green(), summary)
+            }
+            Err(err) => {
+                failed += 1;
+                println!("{} {} ({})", "✗".red(), summary, err);
+            }
+        }
+    }
+
+    operation.record()?;
+
+    let imported = format!(
+        "Imported {} of {} worklog(s)",
+        entries.len() - failed,
+        entries.len()
+    );
+    if failed == 0 {
+        println!("{}", imported.green());
+    } else {
+        println!("{}", imported.yellow());
+    }
+
+    Ok(())
+}
+
+impl ImportEntry {
+    /// The row shown in the calendar preview.
+    fn as_worklog(&self) -> WorkLog {
+        WorkLog {
+            day: self
+                .date
+                .and_hms_opt(8, 0, 0)
+                .expect("valid time")
+                .and_utc(),
+            task: self.task.clone(),
+            time_spent: format_time(self.time_spent),
+            time_spent_seconds: self.time_spent,
+            comment: self.comment.clone(),
+            state: WorkLogState::Pending,
+            ..Default::default()
+        }
+    }
+}
+
+fn parse_csv(content: &str) -> Result<Vec<ImportRow>> {
+    csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes())
+        .deserialize()
+        .enumerate()
+        .map(|(i, row)| row.map_err(|err| anyhow::anyhow!("Row {}: {}", i + 1, err)))
+        .collect()
+}
+
+fn parse_json(content: &str) -> Result<Vec<ImportRow>> {
+    serde_json::from_str(content).map_err(|err| anyhow::anyhow!("Invalid JSON: {}", err))
+}
+
+/// Parses every row, collecting all errors instead of stopping at the first.
+fn validate(
+    rows: Vec<ImportRow>,
+    units: impl Fn() -> WorkUnits + Copy,
+) -> (Vec<ImportEntry>, Vec<String>) {
+    let mut entries = Vec::new();
+    let mut errors = Vec::new();
+    for (i, row) in rows.into_iter().enumerate() {
+        match validate_row(i + 1, row, units) {
+            Ok(entry) => entries.push(entry),
+            Err(err) => errors.push(format!("Row {}: {}", i + 1, err)),
+        }
+    }
+    (entries, errors)
+}
+
+fn validate_row(row: usize, raw: ImportRow, units: impl Fn() -> WorkUnits) -> Result<ImportEntry> {
+    let date = single_date(raw.date.trim())?;
+    let task = raw.task.trim().to_uppercase();
+    if task.is_empty() {
+        anyhow::bail!("Missing task");
+    }
+    let time_spent = parse_duration(raw.time.trim(), units)?;
+    if time_spent == 0 {
+        anyhow::bail!("Missing time");
+    }
+    Ok(ImportEntry {
+        row,
+        date,
+        task,
+        time_spent,
+        comment: raw.comment.filter(|c| !c.trim().is_empty()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_csv_collects_every_error() -> Result<()> {
+        let rows = parse_csv(
+            "date,task,duration,comment\n\
+             03-03-2025,xx-1,1h30m,\"review, planning\"\n\
+             04-03-2025,XX-2,2h,\n\
+             1-3,XX-3,1h,\n\
+             05-03-2025,XX-4,2x,\n",
+        )?;
+
+        let (entries, errors) = validate(rows, WorkUnits::default);
+
+        assert_eq!(
+            entries,
+            vec![
+                ImportEntry {
+                    row: 1,
+                    date: NaiveDate::from_ymd_opt(2025, 3, 3).unwrap(),
+                    task: "XX-1".to_string(),
+                    time_spent: 5400,
+                    comment: Some("review, planning".to_string()),
+                },
+                ImportEntry {
+                    row: 2,
+                    date: NaiveDate::from_ymd_opt(2025, 3, 4).unwrap(),
+                    task: "XX-2".to_string(),
+                    time_spent: 7200,
+                    comment: None,
+                },
+            ]
+        );
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].starts_with("Row 3:"));
+        assert!(errors[1].starts_with("Row 4:"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_json_rows() -> Result<()> {
+        let rows = parse_json(
+            r#"[{"date": "03-03-2025", "task": "XX-1", "time": "1h", "comment": "retro"}]"#,
+        )?;
+
+        let (entries, errors) = validate(rows, WorkUnits::default);
+        assert!(errors.is_empty());
+        assert_eq!(entries[0].comment, Some("retro".to_string()));
+        assert!(parse_json(r#"[{"date": "03-03-2025"}]"#).is_err());
+        Ok(())
+    }
+}
@@ -1,47 +1,47 @@
+use crate::api::jira::is_network_error;
 use crate::api::nager::NagerHoliday;
 use crate::api::{Jira, Nager};
-use anyhow::{Context, Result};
+use crate::cli::LogArgs;
+use crate::commands::prompt::confirm;
+use crate::config::Config;
+use crate::duration::{format_time, is_rest, parse_duration, parse_time, split_evenly, WorkUnits};
+use crate::models::{DateRange, Task, WorkLog, WorkLogList, WorkLogListExt};
+use crate::state::days_off::{DayOffKind, DaysOff};
+use crate::state::journal::Operation;
+use crate::state::outbox::Outbox;
+use anyhow::Result;
 use chrono::{Datelike, Duration, NaiveDate, Utc};
 use colored::Colorize;
-use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
-
-pub fn execute(
-    api: &Jira,
-    nager: &Nager,
-    cli_task: &Option<String>,
-    time: &str,
-    cli_day: &Option<String>,
-    cli_comment: &Option<String>,
-    yes: &bool,
-) -> Result<()> {
-    let task = match cli_task {
-        Some(t) => t,
-        None => {
-            let actually_works = api.actually_works()?;
-            if actually_works.is_empty() {
-                anyhow::bail!("No tasks found. Please set up your tasks first.");
-            }
+use dialoguer::{theme::ColorfulTheme, Confirm, FuzzySelect, Input, Select};
 
-            let days: Vec<String> = actually_works
-                .iter()
-                .map(|d| format!("({}) {}", d.id, d.name))
-                .chain(std::iter::once("Cancel operation".to_string()))
-                .collect();
-
-            let day = Select::with_theme(&ColorfulTheme::default())
-                .with_prompt("Please select the task you want to log time for:")
-                .items(&days)
-                .default(0)
-                .interact()?;
-            if day == days.len() - 1 {
+const DEFAULT_TIME: &str = "8h";
+
+/// Returns whether any worklog was posted, queued or, in dry-run mode, printed.
+pub fn execute(config: &Config, api: &Jira, nager: &Nager, args: &LogArgs) -> Result<bool> {
+    let args = &args.with_allocations()?;
+    let template = match args.task.as_deref() {
+        Some(task) if task.starts_with('@') => Some(config.template(task)?),
+        _ => None,
+    };
+    let time = args
+        .time()
+        .or_else(|| template.and_then(|t| t.time.as_deref()));
+    let yes = &args.yes;
+    let task = &match template
+        .map(|t| t.task.clone())
+        .or_else(|| args.task.clone())
+    {
+        Some(t) => t,
+        None => match select_task(api, config.recent_weeks())? {
+            Some(t) => t,
+            None => {
                 println!("Aborted.");
-                return Ok(());
+                return Ok(false);
             }
-            &actually_works[day].id.clone()
-        }
+        },
     };
 
-    let day = match cli_day.clone() {
+    let day = match args.day() {
         Some(d) => d,
         None => Input::with_theme(&ColorfulTheme::default())
             .with_prompt("Please enter the day(s) you want to log time for (e.g., 2 or 2-5):")
@@ -49,81 +49,288 @@ pub fn execute(
             .interact_text()?,
     };
 
-    let time_spent = parse_time(time)?;
-    let mut dates = parse_date(day.as_str(), true)?;
+    let units = || api.work_units();
+    let shares = parse_tasks(task, units)?;
+    let rest = time.is_some_and(is_rest);
+    let total = match time {
+        Some(time) if !rest => Some(parse_duration(time, units)?),
+        _ => None,
+    };
+    if !rest {
+        allocate(&shares, total)?;
+    }
+    let task = &shares
+        .iter()
+        .map(|(task, _)| task.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut dates = match args.reference_month()? {
+        Some(month) => parse_date_in(day.as_str(), true, month)?,
+        None => parse_date(day.as_str(), true)?,
+    };
 
     match check_weekends(&mut dates) {
         Ok(_) => {}
         Err(err) => {
             println!("{}", err);
-            return Ok(());
+            return Ok(false);
         }
     };
 
     if dates.is_empty() {
         println!("Nothing to log");
-        return Ok(());
+        return Ok(false);
     }
 
     match check_holidays(nager, &mut dates) {
         Ok(_) => (),
         Err(err) => {
             println!("{}", err);
-            return Ok(());
+            return Ok(false);
         }
     };
 
-    for date in dates.clone() {
+    match check_days_off(&DaysOff::load()?, &mut dates) {
+        Ok(_) => (),
+        Err(err) => {
+            println!("{}", err);
+            return Ok(false);
+        }
+    };
+
+    if dates.is_empty() {
+        println!("Nothing to log");
+        return Ok(false);
+    }
+
+    let existing = match api.fetch_worklogs(DateRange {
+        from: *dates.iter().min().expect("No date found"),
+        to: *dates.iter().max().expect("No date found"),
+    }) {
+        Ok(worklogs) => Some(worklogs),
+        Err(err) if rest => {
+            return Err(err.context("Can't work out the rest of the day without existing worklogs"))
+        }
+        Err(err) => {
+            println!("{} {}", "Can't check existing worklogs:".yellow(), err);
+            None
+        }
+    };
+
+    let schedule = config.schedule()?;
+    let mut entries: Vec<(NaiveDate, String, u64)> = Vec::new();
+    for date in &dates {
+        let total = if rest {
+            let logged = existing.as_ref().map_or(0, |w| w.logged_on(*date));
+            let left = schedule.expected_on(*date).saturating_sub(logged);
+            if left < 60 {
+                println!(
+                    "Nothing left to log on {}",
+                    date.format("%Y-%m-%d").to_string().yellow()
+                );
+                continue;
+            }
+            Some(left)
+        } else {
+            total
+        };
+        for (task, time) in allocate(&shares, total)? {
+            entries.push((*date, task, time));
+        }
+    }
+
+    let replaced = match &existing {
+        Some(existing) => match check_existing(existing, config.daily_cap()?, &mut entries) {
+            Ok(replaced) => replaced,
+            Err(err) => {
+                println!("{}", err);
+                return Ok(false);
+            }
+        },
+        None => Vec::new(),
+    };
+
+    if entries.is_empty() {
+        println!("Nothing to log");
+        return Ok(false);
+    }
+
+    for (date, task, time) in &entries {
         println!(
             "Logging {} on {} for task {}",
-            time.green(),
+            format_time(*time).green(),
             date.format("%Y-%m-%d").to_string().green(),
             task.green()
         );
     }
 
-    let comment = match cli_comment.clone() {
-        Some(c) => Some(c),
-        None => {
-            if task.contains("TD-") {
-                Some(
-                    Input::with_theme(&ColorfulTheme::default())
-                        .with_prompt("Please enter comment for TD task (e.g., retro):")
-                        .default("review, planning".to_string())
-                        .interact_text()?,
-                )
-            } else {
-                None
-            }
-        }
+    let comment = match (args.comment(), template) {
+        (Some(c), _) => Some(c),
+        (None, Some(template)) if template.ask_comment => Some(
+            Input::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!("Please enter comment for {}:", task))
+                .default(template.comment.clone().unwrap_or_default())
+                .interact_text()?,
+        ),
+        (None, Some(template)) => template.comment.clone(),
+        (None, None) => None,
     };
 
-    if !*yes
-        && !Confirm::with_theme(&ColorfulTheme::default())
-            .with_prompt("Are you sure?")
-            .default(true)
-            .show_default(true)
-            .wait_for_newline(true)
-            .interact()?
-    {
+    if !confirm(yes)? {
         println!("Aborted.");
-        return Ok(());
+        return Ok(false);
+    }
+
+    let mut operation = Operation::new("log");
+    for worklog in &replaced {
+        if let Err(err) = api.delete_worklog(
+            worklog.issue(),
+            &worklog.id,
+            worklog.day.date_naive(),
+            worklog.time_spent_seconds,
+        ) {
+            operation.record()?;
+            return Err(err.context(format!(
+                "Failed to replace worklog on {}",
+                worklog.day.format("%Y-%m-%d")
+            )));
+        }
+        if !api.is_dry_run() {
+            operation.replace(worklog);
+        }
     }
 
-    for date in dates {
-        api.log_worktime(task, time_spent, &date, comment.clone())
-            .context(format!(
-                "Failed to log time for {}",
-                date.format("%Y-%m-%d")
-            ))?;
+    let mut result = Ok(());
+    for (i, (date, task, time)) in entries.iter().enumerate() {
+        match api.log_worktime(task, *time, date, comment.clone()) {
+            Ok(worklog_id) => operation.add(task, worklog_id, *date, *time),
+            Err(err) if is_network_error(&err) && confirm_queue(&err, yes)? => {
+                let mut outbox = Outbox::load()?;
+                for (date, task, time) in &entries[i..] {
+                    outbox.push(task, *time, *date, comment.clone());
+                }
+                outbox.save()?;
+                operation.record()?;
+                println!(
+                    "Queued {} worklog(s). Run `{}` once Jira is reachable.",
+                    (entries.len() - i).to_string().yellow(),
+                    "jtime sync".green()
+                );
+                return Ok(true);
+            }
+            Err(err) => {
+                result = Err(err.context(format!(
+                    "Failed to log time to {} for {}",
+                    task,
+                    date.format("%Y-%m-%d")
+                )));
+                break;
+            }
+        }
     }
+    operation.record()?;
+    result?;
 
     println!(
         "{}",
         "Logged time successfully! Time for coffee! ☕".green()
     );
 
-    Ok(())
+    Ok(true)
+}
+
+/// Offers to keep worklogs in the outbox when Jira can't be reached.
+fn confirm_queue(err: &anyhow::Error, yes: &bool) -> Result<bool> {
+    println!("{} {}", "Jira is unreachable:".yellow(), err);
+    Ok(*yes
+        || Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt("Queue the worklog(s) locally and send them later with `jtime sync`?")
+            .default(true)
+            .show_default(true)
+            .wait_for_newline(true)
+            .interact()?)
+}
+
+/// Fuzzy-searchable picker over the picker JQL issues, with issues logged to in
+/// the last `recent_weeks` weeks ranked first. Any other issue can be entered by key.
+fn select_task(api: &Jira, recent_weeks: u32) -> Result<Option<String>> {
+    let today = Utc::now().date_naive();
+    let recent = api
+        .fetch_worklogs(DateRange {
+            from: today - Duration::weeks(recent_weeks as i64),
+            to: today,
+        })
+        .unwrap_or_default();
+
+    let mut tasks = api.actually_works()?;
+    let mut missing: Vec<String> = recent
+        .iter()
+        .map(|w| w.task.clone())
+        .filter(|key| tasks.iter().all(|t| &t.id != key))
+        .collect();
+    missing.sort();
+    missing.dedup();
+    if !missing.is_empty() {
+        tasks.extend(api.get_tasks(&missing).unwrap_or_default());
+    }
+    let tasks = rank_tasks(tasks, &recent);
+
+    let items: Vec<String> = tasks
+        .iter()
+        .map(|t| t.label())
+        .chain([
+            "Other issue (enter key)".to_string(),
+            "Cancel operation".to_string(),
+        ])
+        .collect();
+
+    let selection = FuzzySelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("Please select the task you want to log time for (type to filter):")
+        .items(&items)
+        .default(0)
+        .interact()?;
+
+    if selection == items.len() - 1 {
+        return Ok(None);
+    }
+    if selection < tasks.len() {
+        return Ok(Some(tasks[selection].id.clone()));
+    }
+
+    let key: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("Please enter the issue key (e.g., XX-1234):")
+        .interact_text()?;
+    let task = api
+        .get_tasks(&[key.trim().to_uppercase()])?
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("Issue {} not found", key.trim()))?;
+
+    if Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt(format!("Log time to {}?", task.label()))
+        .default(true)
+        .interact()?
+    {
+        Ok(Some(task.id))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Orders tasks by their latest worklog (most recent first), keeping the
+/// original order for tasks without worklogs.
+fn rank_tasks(tasks: Vec<Task>, recent: &WorkLogList) -> Vec<Task> {
+    let mut tasks = tasks;
+    tasks.sort_by_key(|task| {
+        std::cmp::Reverse(
+            recent
+                .iter()
+                .filter(|w| w.task == task.id)
+                .map(|w| w.day)
+                .max(),
+        )
+    });
+    tasks
 }
 
 fn check_weekends(dates: &mut Vec<NaiveDate>) -> Result<()> {
@@ -170,9 +377,15 @@ fn check_holidays(nager: &Nager, dates: &mut Vec<NaiveDate>) -> Result<()> {
         return Ok(());
     }
 
-    let first_date = dates.first().expect("No date found");
-    let holidays = match nager.get_all_holidays(first_date.format("%Y").to_string()) {
-        Ok(h) => h,
+    let mut years: Vec<i32> = dates.iter().map(|d| d.year()).collect();
+    years.sort();
+    years.dedup();
+    let holidays = match years
+        .iter()
+        .map(|year| nager.get_all_holidays(year.to_string()))
+        .collect::<Result<Vec<_>>>()
+    {
+        Ok(h) => h.into_iter().flatten().collect::<Vec<_>>(),
         Err(err) => {
             println!("{}", err);
             if !Confirm::with_theme(&ColorfulTheme::default())
@@ -229,85 +442,576 @@ fn check_holidays(nager: &Nager, dates: &mut Vec<NaiveDate>) -> Result<()> {
     Ok(())
 }
 
-pub fn parse_date(date_str: &str, with_weekend: bool) -> Result<Vec<NaiveDate>> {
-    let now = Utc::now().naive_utc().date();
-    let (year, month) = (now.year(), now.month());
+fn check_days_off(days_off: &DaysOff, dates: &mut Vec<NaiveDate>) -> Result<()> {
+    let off_dates: Vec<(NaiveDate, DayOffKind)> = dates
+        .iter()
+        .filter_map(|d| days_off.get(*d).map(|kind| (*d, kind)))
+        .collect();
 
-    let parse_single_date = |s: &str| -> Result<NaiveDate> {
-        if let Ok(day) = s.parse::<u32>() {
-            NaiveDate::from_ymd_opt(year, month, day)
-                .ok_or_else(|| anyhow::anyhow!("Invalid day {} for the current month", day))
-        } else {
-            match s {
-                "today" => Ok(now),
-                "yesterday" => Ok(now - Duration::days(1)),
-                _ => Ok(NaiveDate::parse_from_str(s, "%d-%m-%Y")
-                    .map_err(|_| anyhow::anyhow!("Invalid date format: {}", s))?),
+    if !off_dates.is_empty() {
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!(
+                "Hey! You're trying to log time on your day(s) off:\n{}\n\nWhat do you want to do? :)",
+                off_dates
+                    .iter()
+                    .map(|(date, kind)| format!("{} - {}", date.format("%Y-%m-%d"), kind))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+                    .green()
+            ))
+            .default(0)
+            .items(&["Skip days off", "Keep days off", "Cancel operation"])
+            .interact()?;
+
+        match selection {
+            0 => dates.retain(|d| days_off.get(*d).is_none()),
+            1 => {} // Keep all dates
+            _ => {
+                anyhow::bail!("Aborted.")
             }
-        }
+        };
+    }
+
+    Ok(())
+}
+
+/// How much of the logged time goes to a task in `XX-1:4h,XX-2:50%,XX-3`.
+#[derive(Debug, PartialEq)]
+enum Share {
+    Fixed(u64),
+    Percent(u64),
+    /// Even part of whatever the other tasks leave
+    Rest,
+}
+
+fn parse_tasks(spec: &str, units: impl Fn() -> WorkUnits + Copy) -> Result<Vec<(String, Share)>> {
+    spec.split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let Some((task, amount)) = part.split_once(':') else {
+                return Ok((part.to_string(), Share::Rest));
+            };
+            let share = match amount.trim().strip_suffix('%') {
+                Some(percent) => Share::Percent(
+                    percent
+                        .trim()
+                        .parse()
+                        .ok()
+                        .filter(|p| *p <= 100)
+                        .ok_or_else(|| anyhow::anyhow!("Invalid percentage: {}", amount))?,
+                ),
+                None => Share::Fixed(parse_duration(amount, units)?),
+            };
+            Ok((task.trim().to_string(), share))
+        })
+        .collect()
+}
+
+/// Turns the shares into durations adding up exactly to `total`. Without a
+/// total, fixed shares log their sum and anything else splits `DEFAULT_TIME`.
+fn allocate(tasks: &[(String, Share)], total: Option<u64>) -> Result<Vec<(String, u64)>> {
+    let fixed: u64 = tasks
+        .iter()
+        .map(|(_, share)| match share {
+            Share::Fixed(time) => *time,
+            _ => 0,
+        })
+        .sum();
+    let percent: u64 = tasks
+        .iter()
+        .map(|(_, share)| match share {
+            Share::Percent(percent) => *percent,
+            _ => 0,
+        })
+        .sum();
+    let rest = tasks.iter().filter(|(_, s)| *s == Share::Rest).count();
+    let total = match total {
+        Some(total) => total,
+        None if rest == 0 && percent == 0 => fixed,
+        None => parse_time(DEFAULT_TIME)?,
     };
 
-    if let Some(idx) = date_str.find('-') {
-        let (start_str, end_str) = date_str.split_at(idx);
-        let end_str = &end_str[1..];
-        if let (Ok(start_day), Ok(end_day)) = (
-            start_str.trim().parse::<u32>(),
-            end_str.trim().parse::<u32>(),
-        ) {
-            let dates = (start_day..=end_day)
-                .filter_map(|d| NaiveDate::from_ymd_opt(year, month, d))
-                .filter(|d| with_weekend || d.weekday().num_days_from_monday() < 5)
-                .collect::<Vec<_>>();
-            if dates.is_empty() {
-                anyhow::bail!("No valid dates in the specified range");
+    let allocated = fixed * 100 + total * percent;
+    if allocated > total * 100 || (rest == 0 && allocated != total * 100) {
+        anyhow::bail!(
+            "Time per task doesn't add up to {} (got {}{})",
+            format_time(total),
+            format_time(fixed),
+            if percent > 0 {
+                format!(" and {}%", percent)
+            } else {
+                String::new()
+            }
+        );
+    }
+
+    // Percentages are rounded down to whole minutes. The leftover goes to the
+    // tasks without a share, or else to the largest rounding remainders.
+    let mut times: Vec<u64> = tasks
+        .iter()
+        .map(|(_, share)| match share {
+            Share::Fixed(time) => *time,
+            Share::Percent(percent) => total * percent / 6000 * 60,
+            Share::Rest => 0,
+        })
+        .collect();
+    let leftover = total - times.iter().sum::<u64>();
+    if rest > 0 {
+        let mut parts = split_evenly(leftover, rest).into_iter();
+        for (time, (_, share)) in times.iter_mut().zip(tasks) {
+            if *share == Share::Rest {
+                *time = parts.next().unwrap_or(0);
             }
-            Ok(dates)
-        } else {
-            let start_date = parse_single_date(start_str.trim())?;
-            let end_date = parse_single_date(end_str.trim())?;
-            Ok(vec![start_date, end_date])
         }
     } else {
-        Ok(vec![parse_single_date(date_str)?])
+        let mut order: Vec<usize> = (0..tasks.len())
+            .filter(|i| matches!(tasks[*i].1, Share::Percent(_)))
+            .collect();
+        order.sort_by_key(|i| match tasks[*i].1 {
+            Share::Percent(percent) => std::cmp::Reverse(total * percent % 6000),
+            _ => std::cmp::Reverse(0),
+        });
+        for i in order.iter().take((leftover / 60) as usize) {
+            times[*i] += 60;
+        }
+        if let Some(i) = order.first() {
+            times[*i] += leftover % 60;
+        }
+    }
+
+    let allocations: Vec<(String, u64)> = tasks
+        .iter()
+        .zip(times)
+        .filter(|(_, time)| *time > 0)
+        .map(|((task, _), time)| (task.clone(), time))
+        .collect();
+    if allocations.is_empty() {
+        anyhow::bail!("No time to log");
     }
+    Ok(allocations)
 }
 
-pub fn parse_time(time_str: &str) -> anyhow::Result<u64> {
-    let mut seconds = 0;
-    let mut current_num = String::new();
+/// A day where the new worklogs duplicate existing ones or exceed the cap.
+#[derive(Debug)]
+struct Conflict {
+    date: NaiveDate,
+    /// Worklogs of the same tasks already on the day
+    duplicates: WorkLogList,
+    /// Day total once the new worklogs are added
+    total: u64,
+    over_cap: bool,
+}
 
-    for c in time_str.chars() {
-        if c.is_ascii_digit() {
-            current_num.push(c);
-        } else {
-            if current_num.is_empty() {
-                return Err(anyhow::anyhow!("No number provided for time unit '{}'", c));
+fn find_conflicts(
+    entries: &[(NaiveDate, String, u64)],
+    worklogs: &WorkLogList,
+    cap: Option<u64>,
+) -> Vec<Conflict> {
+    let mut dates: Vec<NaiveDate> = entries.iter().map(|(date, _, _)| *date).collect();
+    dates.dedup();
+    dates
+        .into_iter()
+        .map(|date| {
+            let planned: Vec<&(NaiveDate, String, u64)> =
+                entries.iter().filter(|(d, _, _)| *d == date).collect();
+            let total =
+                worklogs.logged_on(date) + planned.iter().map(|(_, _, time)| time).sum::<u64>();
+            Conflict {
+                date,
+                duplicates: worklogs
+                    .get_by_day(date)
+                    .into_iter()
+                    .filter(|w| {
+                        planned
+                            .iter()
+                            .any(|(_, task, _)| w.task.eq_ignore_ascii_case(task))
+                    })
+                    .collect(),
+                total,
+                over_cap: cap.is_some_and(|cap| total > cap),
             }
-            let num: u64 = current_num
-                .parse()
-                .map_err(|e| anyhow::anyhow!("Failed to parse number: {}", e))?;
-            match c {
-                'h' => seconds += num * 3600,
-                'm' => seconds += num * 60,
-                _ => return Err(anyhow::anyhow!("Invalid time unit: '{}'", c)),
+        })
+        .filter(|c| !c.duplicates.is_empty() || c.over_cap)
+        .collect()
+}
+
+/// Warns about days already logged to the tasks or going over `daily_cap`.
+/// Returns the worklogs to delete before posting when replacing them.
+fn check_existing(
+    worklogs: &WorkLogList,
+    cap: Option<u64>,
+    entries: &mut Vec<(NaiveDate, String, u64)>,
+) -> Result<Vec<WorkLog>> {
+    let conflicts = find_conflicts(entries, worklogs, cap);
+    if conflicts.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt(format!(
+            "Hey! Some days already have time logged:\n{}\n\nWhat do you want to do? :)",
+            conflicts
+                .iter()
+                .map(|c| {
+                    let mut reasons: Vec<String> = c
+                        .duplicates
+                        .iter()
+                        .map(|w| format!("{} already logged ({})", w.task, w.time_spent))
+                        .collect();
+                    if c.over_cap {
+                        reasons.push(format!(
+                            "{} in total, over the daily cap",
+                            format_time(c.total)
+                        ));
+                    }
+                    format!("{} - {}", c.date.format("%Y-%m-%d"), reasons.join(", "))
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+                .green()
+        ))
+        .default(0)
+        .items(&[
+            "Skip these days",
+            "Replace existing worklogs of this task",
+            "Continue anyway",
+            "Cancel operation",
+        ])
+        .interact()?;
+
+    match selection {
+        0 => {
+            entries.retain(|(d, _, _)| conflicts.iter().all(|c| c.date != *d));
+            Ok(Vec::new())
+        }
+        1 => Ok(conflicts.into_iter().flat_map(|c| c.duplicates).collect()),
+        2 => Ok(Vec::new()),
+        _ => anyhow::bail!("Aborted."),
+    }
+}
+
+/// Parses day expressions, relative to today:
+/// - `5`, `today`, `yesterday`, `tomorrow`, `-2`, `+1`
+/// - `mon`, `last fri`, `next tue` (weeks start on Monday)
+/// - `03-03-2025`, `2025-03-03`
+/// - ranges `2-5`, `mon-wed`, `28-01-2025..03-02-2025`, `28-3` (from the
+///   previous month), and `this week`, `last week`, `this month`, `last month`
+/// - comma lists of any of the above: `2,4,9-11`
+///
+/// Without `with_weekend`, weekends are left out of ranges.
+pub fn parse_date(date_str: &str, with_weekend: bool) -> Result<Vec<NaiveDate>> {
+    let today = Utc::now().naive_utc().date();
+    parse_date_at(date_str, with_weekend, today, today)
+}
+
+/// Like `parse_date`, with day numbers in the month of `month` instead of
+/// the current one.
+pub fn parse_date_in(
+    date_str: &str,
+    with_weekend: bool,
+    month: NaiveDate,
+) -> Result<Vec<NaiveDate>> {
+    parse_date_at(date_str, with_weekend, Utc::now().naive_utc().date(), month)
+}
+
+/// A day expression that must resolve to exactly one date, weekends included.
+pub fn single_date(day: &str) -> Result<NaiveDate> {
+    match parse_date(day, true)?.as_slice() {
+        [date] => Ok(*date),
+        _ => anyhow::bail!("Please provide a single day, got {}", day),
+    }
+}
+
+/// Range of `--from` and `--to`, by default from the first of the month to today.
+pub fn date_range(from: &Option<String>, to: &Option<String>) -> Result<DateRange> {
+    let today = Utc::now().date_naive();
+    let range = DateRange {
+        from: match from {
+            Some(day) => single_date(day)?,
+            None => today.with_day(1).expect("first day of month"),
+        },
+        to: match to {
+            Some(day) => single_date(day)?,
+            None => today,
+        },
+    };
+    if range.from > range.to {
+        anyhow::bail!("--from must not be after --to");
+    }
+    Ok(range)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum DateToken {
+    /// Value and number of digits
+    Number(u32, usize),
+    Word(String),
+    Dash,
+    Plus,
+    Comma,
+    Ellipsis,
+}
+
+fn tokenize_date(date_str: &str) -> Result<Vec<DateToken>> {
+    let mut tokens = Vec::new();
+    let mut chars = date_str.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '-' => tokens.push(DateToken::Dash),
+            '+' => tokens.push(DateToken::Plus),
+            ',' => tokens.push(DateToken::Comma),
+            '.' if chars.next_if_eq(&'.').is_some() => tokens.push(DateToken::Ellipsis),
+            c if c.is_ascii_digit() => {
+                let mut digits = c.to_string();
+                while let Some(c) = chars.next_if(|c| c.is_ascii_digit()) {
+                    digits.push(c);
+                }
+                let value = digits
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("Invalid number: {}", digits))?;
+                tokens.push(DateToken::Number(value, digits.len()));
+            }
+            c if c.is_alphabetic() => {
+                let mut word = c.to_lowercase().to_string();
+                while let Some(c) = chars.next_if(|c| c.is_alphabetic()) {
+                    word.extend(c.to_lowercase());
+                }
+                tokens.push(DateToken::Word(word));
             }
-            current_num.clear();
+            _ => anyhow::bail!("Unexpected '{}' in date: {}", c, date_str),
         }
     }
+    Ok(tokens)
+}
+
+/// A day number stays unresolved until we know whether it starts a range
+/// reaching into the previous month.
+enum DatePoint {
+    Day(u32),
+    Date(NaiveDate),
+}
+
+struct DateParser {
+    tokens: Vec<DateToken>,
+    pos: usize,
+    today: NaiveDate,
+    /// Any day of the month day numbers belong to
+    month: NaiveDate,
+}
 
-    if !current_num.is_empty() {
-        return Err(anyhow::anyhow!(
-            "Incomplete time format, missing unit for '{}'",
-            current_num
-        ));
+impl DateParser {
+    fn peek(&self, offset: usize) -> Option<&DateToken> {
+        self.tokens.get(self.pos + offset)
     }
 
-    Ok(seconds)
+    fn next(&mut self) -> Option<DateToken> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn is_word(&self, offset: usize, words: &[&str]) -> bool {
+        matches!(self.peek(offset), Some(DateToken::Word(w)) if words.contains(&w.as_str()))
+    }
+
+    fn monday(&self, weeks: i64) -> NaiveDate {
+        self.today - Duration::days(self.today.weekday().num_days_from_monday() as i64)
+            + Duration::weeks(weeks)
+    }
+
+    fn day_of_month(&self, date: NaiveDate, day: u32) -> Result<NaiveDate> {
+        date.with_day(day)
+            .ok_or_else(|| anyhow::anyhow!("Invalid day {} for {}", day, date.format("%B %Y")))
+    }
+
+    /// Returns the first and last day of an item.
+    fn item(&mut self) -> Result<(NaiveDate, NaiveDate)> {
+        if self.is_word(0, &["this", "last", "next"]) && self.is_word(1, &["week", "month"]) {
+            let offset = match self.next() {
+                Some(DateToken::Word(w)) if w == "last" => -1,
+                Some(DateToken::Word(w)) if w == "next" => 1,
+                _ => 0,
+            };
+            return if self.is_word(0, &["week"]) {
+                self.pos += 1;
+                let monday = self.monday(offset);
+                Ok((monday, monday + Duration::days(6)))
+            } else {
+                self.pos += 1;
+                let first = self.today.with_day(1).expect("valid day");
+                let first = match offset {
+                    -1 => first - chrono::Months::new(1),
+                    1 => first + chrono::Months::new(1),
+                    _ => first,
+                };
+                let last = first + chrono::Months::new(1) - Duration::days(1);
+                Ok((first, last))
+            };
+        }
+
+        let start = self.point()?;
+        let is_range = matches!(self.peek(0), Some(DateToken::Dash | DateToken::Ellipsis))
+            || self.is_word(0, &["to"]);
+        if !is_range {
+            let date = self.resolve(start, self.month)?;
+            return Ok((date, date));
+        }
+        self.pos += 1;
+
+        // Range ends past the month's last day are clamped to it, so that
+        // `1-31` covers any month.
+        let end_point = self.point()?;
+        let end_day = match &end_point {
+            DatePoint::Day(day) => *day,
+            DatePoint::Date(date) => date.day(),
+        };
+        let end = match end_point {
+            DatePoint::Day(day @ 29..=31) => self.month.with_day(day).unwrap_or_else(|| {
+                let first = self.month.with_day(1).expect("valid day");
+                first + chrono::Months::new(1) - Duration::days(1)
+            }),
+            point => self.resolve(point, self.month)?,
+        };
+        let start = match start {
+            DatePoint::Day(day) if day > end_day => {
+                self.resolve(start, end - chrono::Months::new(1))?
+            }
+            _ => self.resolve(start, end)?,
+        };
+        if start > end {
+            anyhow::bail!(
+                "Range starts after it ends: {} > {}",
+                start.format("%Y-%m-%d"),
+                end.format("%Y-%m-%d")
+            );
+        }
+        Ok((start, end))
+    }
+
+    /// Day numbers are resolved in the month of `month`.
+    fn resolve(&self, point: DatePoint, month: NaiveDate) -> Result<NaiveDate> {
+        match point {
+            DatePoint::Day(day) => self.day_of_month(month, day),
+            DatePoint::Date(date) => Ok(date),
+        }
+    }
+
+    fn point(&mut self) -> Result<DatePoint> {
+        match self.next() {
+            Some(DateToken::Dash) => {
+                Ok(DatePoint::Date(self.today - Duration::days(self.offset()?)))
+            }
+            Some(DateToken::Plus) => {
+                Ok(DatePoint::Date(self.today + Duration::days(self.offset()?)))
+            }
+            Some(DateToken::Number(first, first_len)) => {
+                let (
+                    Some(DateToken::Dash),
+                    Some(DateToken::Number(second, _)),
+                    Some(DateToken::Dash),
+                    Some(DateToken::Number(third, third_len)),
+                ) = (self.peek(0), self.peek(1), self.peek(2), self.peek(3))
+                else {
+                    return Ok(DatePoint::Day(first));
+                };
+                let (year, month, day) = match (first_len, third_len) {
+                    (4, _) => (first, *second, *third),
+                    (_, 4) => (*third, *second, first),
+                    _ => return Ok(DatePoint::Day(first)),
+                };
+                self.pos += 4;
+                NaiveDate::from_ymd_opt(year as i32, month, day)
+                    .map(DatePoint::Date)
+                    .ok_or_else(|| anyhow::anyhow!("Invalid date: {}-{}-{}", year, month, day))
+            }
+            Some(DateToken::Word(word)) => {
+                let weeks = match word.as_str() {
+                    "today" => return Ok(DatePoint::Date(self.today)),
+                    "yesterday" => return Ok(DatePoint::Date(self.today - Duration::days(1))),
+                    "tomorrow" => return Ok(DatePoint::Date(self.today + Duration::days(1))),
+                    "last" => -1,
+                    "next" => 1,
+                    "this" => 0,
+                    _ => {
+                        self.pos -= 1;
+                        0
+                    }
+                };
+                match self.next() {
+                    Some(DateToken::Word(weekday)) => {
+                        let weekday: chrono::Weekday = weekday
+                            .parse()
+                            .map_err(|_| anyhow::anyhow!("Invalid date format: {}", weekday))?;
+                        Ok(DatePoint::Date(
+                            self.monday(weeks)
+                                + Duration::days(weekday.num_days_from_monday() as i64),
+                        ))
+                    }
+                    _ => anyhow::bail!("Expected a weekday after '{}'", word),
+                }
+            }
+            Some(token) => anyhow::bail!("Unexpected {:?} in date", token),
+            None => anyhow::bail!("Missing date"),
+        }
+    }
+
+    fn offset(&mut self) -> Result<i64> {
+        match self.next() {
+            Some(DateToken::Number(days, _)) => Ok(days as i64),
+            _ => anyhow::bail!("Expected a number of days after '-' or '+'"),
+        }
+    }
 }
 
+fn parse_date_at(
+    date_str: &str,
+    with_weekend: bool,
+    today: NaiveDate,
+    month: NaiveDate,
+) -> Result<Vec<NaiveDate>> {
+    let mut parser = DateParser {
+        tokens: tokenize_date(date_str)?,
+        pos: 0,
+        today,
+        month,
+    };
+    if parser.tokens.is_empty() {
+        anyhow::bail!("Invalid date format: {}", date_str);
+    }
+
+    let mut dates = Vec::new();
+    loop {
+        let (start, end) = parser
+            .item()
+            .map_err(|err| err.context(format!("Invalid date format: {}", date_str)))?;
+        dates.extend(
+            DateRange {
+                from: start,
+                to: end,
+            }
+            .into_iter()
+            .filter(|d| with_weekend || start == end || d.weekday().num_days_from_monday() < 5),
+        );
+        match parser.next() {
+            Some(DateToken::Comma) => continue,
+            None => break,
+            Some(token) => anyhow::bail!("Unexpected {:?} in date: {}", token, date_str),
+        }
+    }
+    dates.sort();
+    dates.dedup();
+
+    if dates.is_empty() {
+        anyhow::bail!("No valid dates in the specified range");
+    }
+    Ok(dates)
+}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::worklog;
     use chrono::{Datelike, Utc};
 
     #[test]
@@ -335,6 +1039,13 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_parse_full_date() -> Result<()> {
+        let parsed = parse_date("03-03-2025", true)?;
+        assert_eq!(parsed, vec![NaiveDate::from_ymd_opt(2025, 3, 3).unwrap()]);
+        Ok(())
+    }
+
     #[test]
     fn test_parse_day_range() -> Result<()> {
         let parsed = parse_date("1-3", true)?;
@@ -382,40 +1093,212 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_time_hours_only() -> Result<()> {
-        // 2 hours = 7200 seconds
-        let seconds = parse_time("2h")?;
-        assert_eq!(seconds, 7200);
+    fn test_parse_date_expressions() -> Result<()> {
+        // Wednesday
+        let today = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+        let date = |y: i32, m: u32, d: u32| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let days = |from: NaiveDate, to: NaiveDate| -> Vec<NaiveDate> {
+            DateRange { from, to }.into_iter().collect()
+        };
+        let parse = |s: &str| parse_date_at(s, true, today, today);
+
+        assert_eq!(parse("2025-03-03")?, vec![date(2025, 3, 3)]);
+        assert_eq!(parse("-2")?, vec![date(2025, 3, 10)]);
+        assert_eq!(parse("+1")?, vec![date(2025, 3, 13)]);
+        assert_eq!(parse("tomorrow")?, vec![date(2025, 3, 13)]);
+        assert_eq!(parse("mon")?, vec![date(2025, 3, 10)]);
+        assert_eq!(parse("last fri")?, vec![date(2025, 3, 7)]);
+        assert_eq!(parse("Next Tuesday")?, vec![date(2025, 3, 18)]);
+        assert_eq!(
+            parse("2,4,9-11")?,
+            vec![
+                date(2025, 3, 2),
+                date(2025, 3, 4),
+                date(2025, 3, 9),
+                date(2025, 3, 10),
+                date(2025, 3, 11)
+            ]
+        );
+        assert_eq!(parse("mon-wed")?, days(date(2025, 3, 10), today));
+        assert_eq!(parse("yesterday-today")?, days(date(2025, 3, 11), today));
+        assert_eq!(
+            parse("this week")?,
+            days(date(2025, 3, 10), date(2025, 3, 16))
+        );
+        assert_eq!(
+            parse("last week")?,
+            days(date(2025, 3, 3), date(2025, 3, 9))
+        );
+        assert_eq!(
+            parse("last month")?,
+            days(date(2025, 2, 1), date(2025, 2, 28))
+        );
+        assert_eq!(
+            parse("28-01-2025..03-02-2025")?,
+            days(date(2025, 1, 28), date(2025, 2, 3))
+        );
+        assert_eq!(
+            parse("28-01-2025 - 2025-02-03")?,
+            days(date(2025, 1, 28), date(2025, 2, 3))
+        );
+        assert_eq!(parse("27-3")?, days(date(2025, 2, 27), date(2025, 3, 3)));
+        assert_eq!(
+            parse_date_at("this week", false, today, today)?,
+            days(date(2025, 3, 10), date(2025, 3, 14))
+        );
+
+        let february = date(2025, 2, 1);
+        assert_eq!(
+            parse_date_at("27-28", true, today, february)?,
+            days(date(2025, 2, 27), date(2025, 2, 28))
+        );
+        assert_eq!(
+            parse_date_at("30-2", true, today, february)?,
+            days(date(2025, 1, 30), date(2025, 2, 2))
+        );
+        assert_eq!(parse_date_at("today", true, today, february)?, vec![today]);
+        assert!(parse_date_at("30", true, today, february).is_err());
+
+        assert!(parse("32").is_err());
+        assert!(parse("someday").is_err());
+        assert!(parse("last").is_err());
+        assert!(parse("2025-03-10..2025-03-01").is_err());
+        assert!(parse("2;3").is_err());
         Ok(())
     }
 
     #[test]
-    fn test_parse_time_minutes_only() -> Result<()> {
-        // 45 minutes = 2700 seconds
-        let seconds = parse_time("45m")?;
-        assert_eq!(seconds, 2700);
+    fn test_parse_range_past_month_end() -> Result<()> {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+        let date = |y: i32, m: u32, d: u32| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let days = |from: NaiveDate, to: NaiveDate| -> Vec<NaiveDate> {
+            DateRange { from, to }.into_iter().collect()
+        };
+
+        let november = date(2025, 11, 1);
+        assert_eq!(
+            parse_date_at("28-31", true, today, november)?,
+            days(date(2025, 11, 28), date(2025, 11, 30))
+        );
+        let february = date(2025, 2, 1);
+        assert_eq!(
+            parse_date_at("1-31", true, today, february)?,
+            days(date(2025, 2, 1), date(2025, 2, 28))
+        );
+        assert!(parse_date_at("31", true, today, november).is_err());
+        assert!(parse_date_at("1-32", true, today, november).is_err());
         Ok(())
     }
 
     #[test]
-    fn test_parse_time_hour_and_minutes() -> Result<()> {
-        // 1 hour and 30 minutes = 3600 + 1800 = 5400 seconds
-        let seconds = parse_time("1h30m")?;
-        assert_eq!(seconds, 5400);
-        Ok(())
+    fn test_rank_tasks_by_recent_usage() {
+        let task = |id: &str| Task {
+            id: id.to_string(),
+            name: id.to_string(),
+            status: None,
+            project: None,
+        };
+        let ranked = rank_tasks(
+            vec![task("XX-1"), task("XX-2"), task("XX-3"), task("XX-4")],
+            &vec![
+                worklog(3, "XX-3", 0),
+                worklog(5, "XX-2", 0),
+                worklog(1, "XX-3", 0),
+            ],
+        );
+        let ids: Vec<_> = ranked.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["XX-2", "XX-3", "XX-1", "XX-4"]);
     }
 
     #[test]
-    fn test_parse_time_invalid_unit() {
-        // Using an invalid unit 's'
-        let result = parse_time("10s");
-        assert!(result.is_err());
+    fn test_allocate() -> Result<()> {
+        let hours = |h: u64| h * 3600;
+        let allocations = allocate(
+            &parse_tasks("XX-1,XX-2,XX-3", WorkUnits::default)?,
+            Some(hours(8)),
+        )?;
+        assert_eq!(
+            allocations,
+            vec![
+                ("XX-1".to_string(), 9600),
+                ("XX-2".to_string(), 9600),
+                ("XX-3".to_string(), 9600)
+            ]
+        );
+
+        let allocations = allocate(
+            &parse_tasks("XX-1:4h, XX-2:3h,XX-3:1h", WorkUnits::default)?,
+            None,
+        )?;
+        assert_eq!(
+            allocations.iter().map(|(_, t)| *t).collect::<Vec<_>>(),
+            vec![hours(4), hours(3), hours(1)]
+        );
+
+        let allocations = allocate(
+            &parse_tasks("XX-1:33%,XX-2:33%,XX-3:34%", WorkUnits::default)?,
+            Some(hours(1)),
+        )?;
+        assert_eq!(
+            allocations.iter().map(|(_, t)| *t).collect::<Vec<_>>(),
+            vec![1200, 1200, 1200]
+        );
+
+        let allocations = allocate(
+            &parse_tasks("XX-1:2h,XX-2:50%,XX-3", WorkUnits::default)?,
+            Some(hours(7)),
+        )?;
+        assert_eq!(
+            allocations.iter().map(|(_, t)| *t).collect::<Vec<_>>(),
+            vec![hours(2), 12600, 5400]
+        );
+
+        assert!(allocate(
+            &parse_tasks("XX-1:4h,XX-2:3h", WorkUnits::default)?,
+            Some(hours(8))
+        )
+        .is_err());
+        assert!(allocate(
+            &parse_tasks("XX-1:60%,XX-2:60%", WorkUnits::default)?,
+            Some(hours(8))
+        )
+        .is_err());
+        assert!(parse_tasks("XX-1:120%", WorkUnits::default).is_err());
+        Ok(())
     }
 
     #[test]
-    fn test_parse_time_incomplete_format() {
-        // Trailing number without unit
-        let result = parse_time("15");
-        assert!(result.is_err());
+    fn test_find_conflicts() {
+        let date = |day: u32| NaiveDate::from_ymd_opt(2025, 3, day).unwrap();
+        let worklog = |day: u32, task: &str, hours: u64| worklog(day, task, hours * 3600);
+        let worklogs = vec![
+            worklog(3, "XX-1", 2),
+            worklog(4, "XX-2", 7),
+            worklog(5, "XX-2", 2),
+        ];
+        let entries = |tasks: &[(&str, u64)]| -> Vec<(NaiveDate, String, u64)> {
+            [date(3), date(4), date(5)]
+                .iter()
+                .flat_map(|d| tasks.iter().map(|(t, h)| (*d, t.to_string(), h * 3600)))
+                .collect()
+        };
+
+        let conflicts = find_conflicts(
+            &entries(&[("xx-1", 1), ("XX-3", 1)]),
+            &worklogs,
+            Some(8 * 3600),
+        );
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(conflicts[0].date, date(3));
+        assert_eq!(conflicts[0].duplicates.len(), 1);
+        assert_eq!(conflicts[0].total, 4 * 3600);
+        assert!(!conflicts[0].over_cap);
+        assert_eq!(conflicts[1].date, date(4));
+        assert!(conflicts[1].duplicates.is_empty());
+        assert_eq!(conflicts[1].total, 9 * 3600);
+        assert!(conflicts[1].over_cap);
+
+        let conflicts = find_conflicts(&entries(&[("XX-3", 2)]), &worklogs, None);
+        assert!(conflicts.is_empty());
     }
 }
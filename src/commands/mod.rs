@@ -1,5 +1,18 @@
 pub mod config;
+pub mod delete;
+pub mod edit;
+pub mod export;
+pub mod fill;
+pub mod import;
 pub mod log;
 pub mod month;
+pub mod off;
+pub mod prompt;
+pub mod recur;
+pub mod report;
+pub mod sync;
+pub mod template;
+pub mod timer;
+pub mod undo;
 pub mod update;
 pub mod week;
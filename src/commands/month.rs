@@ -1,43 +1,54 @@
 use crate::{
     api::Jira,
     cache::Cache,
+    cli::MonthArgs,
+    commands::log::single_date,
     config::Config,
+    state::{days_off::DaysOff, outbox::Outbox, timer::Timer},
     view::{helper::Helper, Calendar, Render},
 };
 use anyhow::Result;
 
 use crate::api::Nager;
-use chrono::{Datelike, Utc};
-
-pub fn execute(
-    config: &Config,
-    api: &Jira,
-    nager: &Nager,
-    use_cache: &bool,
-    month: &Option<u32>,
-) -> Result<()> {
-    let cache = Cache::new("month".to_string());
-
-    if *use_cache {
+use chrono::{Datelike, Months, NaiveDate, Utc};
+
+pub fn execute(config: &Config, api: &Jira, nager: &Nager, args: &MonthArgs) -> Result<()> {
+    let (year, month) = selected_month(args, Utc::now().date_naive())?;
+    let cache = Cache::new(format!("month-{}-{:02}", year, month));
+
+    if args.cache {
         if let Some(data) = cache.load()? {
             println!("{}", data);
             return Ok(());
         }
     }
 
-    let month = month.unwrap_or_else(|| Utc::now().month());
-    let range = Calendar::range_days_for_month(Utc::now().year(), month)?;
-    let tasks = api.fetch_worklogs(range.clone())?;
+    let range = Calendar::range_days_for_month(year, month)?;
+    let mut tasks = api.fetch_worklogs(range.clone())?;
+    tasks.extend(Timer::load()?.map(|timer| timer.as_worklog(Utc::now())));
+    tasks.extend(Outbox::load()?.as_worklogs());
 
     let actually_works = api.actually_works()?;
+    let holiday_map = nager.get_holidays_map_for_range(&range)?;
+    let days_off = DaysOff::load()?;
+    let schedule = config.schedule()?;
     let output = format!(
-        "{}{}",
+        "{}{}{}",
         Calendar::render(
-            range,
-            tasks,
+            range.clone(),
+            tasks.clone(),
             config.show_weekends,
-            Some(nager.get_all_holidays_map(Utc::now().year().to_string())?)
+            Some(holiday_map.clone()),
+            &days_off,
+            &schedule
         )?,
+        Calendar::balance(
+            Calendar::days_of_month(year, month)?,
+            &tasks,
+            &holiday_map,
+            &days_off,
+            &schedule
+        ),
         Calendar::works_on(actually_works)
     );
     println!("{}", output);
@@ -45,3 +56,65 @@ pub fn execute(
 
     Ok(())
 }
+
+/// Year and month picked with `--month`/`--year`, `--offset` or `--date`.
+fn selected_month(args: &MonthArgs, today: NaiveDate) -> Result<(i32, u32)> {
+    let date = match (&args.date, args.offset) {
+        (Some(day), _) => single_date(day)?,
+        (None, Some(offset)) => {
+            let months = Months::new(offset.unsigned_abs());
+            let first = today.with_day(1).expect("valid day");
+            if offset < 0 {
+                first - months
+            } else {
+                first + months
+            }
+        }
+        (None, None) => {
+            return Ok((
+                args.year.unwrap_or(today.year()),
+                args.month.unwrap_or(today.month()),
+            ))
+        }
+    };
+    Ok((date.year(), date.month()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selected_month() -> Result<()> {
+        let today = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let args = |month, year, offset, date: Option<&str>| MonthArgs {
+            cache: false,
+            month,
+            year,
+            offset,
+            date: date.map(str::to_string),
+        };
+
+        assert_eq!(
+            selected_month(&args(None, None, None, None), today)?,
+            (2026, 1)
+        );
+        assert_eq!(
+            selected_month(&args(Some(12), Some(2025), None, None), today)?,
+            (2025, 12)
+        );
+        assert_eq!(
+            selected_month(&args(None, None, Some(-3), None), today)?,
+            (2025, 10)
+        );
+        assert_eq!(
+            selected_month(&args(None, None, Some(12), None), today)?,
+            (2027, 1)
+        );
+        assert_eq!(
+            selected_month(&args(None, None, None, Some("2025-03-14")), today)?,
+            (2025, 3)
+        );
+        Ok(())
+    }
+}
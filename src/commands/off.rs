@@ -0,0 +1,154 @@
+use crate::api::nager::HolidayMap;
+use crate::api::{Jira, Nager};
+use crate::commands::log::parse_date;
+use crate::commands::prompt::confirm;
+use crate::config::Config;
+use crate::duration::format_time;
+use crate::models::DateRange;
+use crate::state::days_off::{DayOffKind, DaysOff};
+use crate::state::journal::Operation;
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use cli_table::{format::Justify, Cell, Style, Table};
+use colored::Colorize;
+
+pub fn add(
+    config: &Config,
+    api: &Jira,
+    nager: &Nager,
+    day: &str,
+    kind: &DayOffKind,
+    yes: &bool,
+) -> Result<()> {
+    let dates = parse_date(day, false)?;
+    let mut days_off = DaysOff::load()?;
+    for date in &dates {
+        days_off.add(*date, *kind);
+        println!(
+            "Added {} on {}",
+            kind.to_string().magenta(),
+            date.format("%Y-%m-%d").to_string().green()
+        );
+    }
+    if !api.is_dry_run() {
+        days_off.save()?;
+    }
+
+    if let Some(task) = &config.absence_task {
+        log_absence(config, api, nager, task, &dates, kind, yes)?;
+    }
+    Ok(())
+}
+
+/// Logs the expected time of each working day off to the absence task.
+fn log_absence(
+    config: &Config,
+    api: &Jira,
+    nager: &Nager,
+    task: &str,
+    dates: &[NaiveDate],
+    kind: &DayOffKind,
+    yes: &bool,
+) -> Result<()> {
+    let (Some(&from), Some(&to)) = (dates.iter().min(), dates.iter().max()) else {
+        return Ok(());
+    };
+    let holidays = match nager.get_holidays_map_for_range(&DateRange { from, to }) {
+        Ok(holidays) => holidays,
+        Err(err) => {
+            println!("{} {}", "Can't check holidays:".yellow(), err);
+            HolidayMap::new()
+        }
+    };
+    let schedule = config.schedule()?;
+    let entries: Vec<(NaiveDate, u64)> = dates
+        .iter()
+        .filter(|d| !holidays.contains_key(&d.format("%Y-%m-%d").to_string()))
+        .map(|d| (*d, schedule.expected_on(*d)))
+        .filter(|(_, time_spent)| *time_spent > 0)
+        .collect();
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    for (date, time_spent) in &entries {
+        println!(
+            "Logging {} on {} for task {}",
+            format_time(*time_spent).green(),
+            date.format("%Y-%m-%d").to_string().green(),
+            task.green()
+        );
+    }
+    if !confirm(yes)? {
+        println!("Days off saved without logging.");
+        return Ok(());
+    }
+
+    let mut operation = Operation::new("off add");
+    let result = entries
+        .iter()
+        .try_for_each(|(date, time_spent)| -> Result<()> {
+            let worklog_id = api
+                .log_worktime(task, *time_spent, date, Some(kind.to_string()))
+                .context(format!(
+                    "Failed to log time for {}",
+                    date.format("%Y-%m-%d")
+                ))?;
+            operation.add(task, worklog_id, *date, *time_spent);
+            Ok(())
+        });
+    operation.record()?;
+    result?;
+    println!("{}", "Logged days off successfully! Enjoy! 🌴".green());
+    Ok(())
+}
+
+pub fn remove(day: &str, dry_run: &bool) -> Result<()> {
+    let mut days_off = DaysOff::load()?;
+    for date in parse_date(day, true)? {
+        match days_off.remove(date) {
+            Some(kind) => println!(
+                "Removed {} on {}",
+                kind.to_string().magenta(),
+                date.format("%Y-%m-%d").to_string().green()
+            ),
+            None => println!("No day off on {}", date.format("%Y-%m-%d")),
+        }
+    }
+    if !dry_run {
+        days_off.save()?;
+    }
+    Ok(())
+}
+
+pub fn list() -> Result<()> {
+    let days_off = DaysOff::load()?;
+    let days = days_off.in_range(&DateRange {
+        from: NaiveDate::MIN,
+        to: NaiveDate::MAX,
+    });
+    if days.is_empty() {
+        println!("No days off yet.");
+        return Ok(());
+    }
+
+    let table = days
+        .iter()
+        .map(|(date, kind)| {
+            vec![
+                date.format("%Y-%m-%d").to_string().cell(),
+                date.format("%a")
+                    .to_string()
+                    .cell()
+                    .justify(Justify::Center),
+                kind.to_string().magenta().cell(),
+            ]
+        })
+        .collect::<Vec<_>>()
+        .table()
+        .title(vec!["Date".cell(), "Day".cell(), "Kind".cell()])
+        .bold(true);
+
+    println!("{}", table.display()?);
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use crate::api::Jira;
+use crate::commands::log::single_date;
+use crate::duration::format_time;
+use crate::models::{DateRange, WorkLog, WorkLogListExt};
+use anyhow::Result;
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
+
+/// Asks for a day (unless given) and lets the user pick one of its worklogs.
+/// Returns `None` when the user cancels the selection.
+pub fn select_worklog(
+    api: &Jira,
+    cli_day: &Option<String>,
+    action: &str,
+) -> Result<Option<WorkLog>> {
+    let day = match cli_day.clone() {
+        Some(d) => d,
+        None => Input::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!(
+                "Please enter the day of the worklog you want to {}:",
+                action
+            ))
+            .default("today".to_string())
+            .interact_text()?,
+    };
+    let date = single_date(&day)?;
+
+    let worklogs = api
+        .fetch_worklogs(DateRange {
+            from: date,
+            to: date,
+        })?
+        .get_by_day(date);
+
+    if worklogs.is_empty() {
+        anyhow::bail!("No worklogs found on {}", date.format("%Y-%m-%d"));
+    }
+
+    let items: Vec<String> = worklogs
+        .iter()
+        .map(|w| {
+            format!(
+                "({}) {}{}",
+                w.task,
+                format_time(w.time_spent_seconds),
+                w.comment
+                    .as_ref()
+                    .map(|c| format!(" - {}", c))
+                    .unwrap_or_default()
+            )
+        })
+        .chain(std::iter::once("Cancel operation".to_string()))
+        .collect();
+
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt(format!("Please select the worklog you want to {}:", action))
+        .items(&items)
+        .default(0)
+        .interact()?;
+
+    if selection == items.len() - 1 {
+        return Ok(None);
+    }
+    Ok(Some(worklogs[selection].clone()))
+}
+
+/// Asks "Are you sure?", unless `--yes` was given.
+pub fn confirm(yes: &bool) -> Result<bool> {
+    Ok(*yes
+        || Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt("Are you sure?")
+            .default(true)
+            .show_default(true)
+            .wait_for_newline(true)
+            .interact()?)
+}
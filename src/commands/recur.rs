@@ -0,0 +1,301 @@
+use crate::api::nager::HolidayMap;
+use crate::api::{Jira, Nager};
+use crate::commands::log::single_date;
+use crate::commands::prompt::confirm;
+use crate::config::{Config, RecurringRule};
+use crate::duration::{format_time, parse_duration, parse_time, WorkUnits};
+use crate::models::{WorkLogList, WorkLogListExt};
+use crate::state::days_off::DaysOff;
+use crate::state::journal::Operation;
+use crate::view::{helper::Helper, Calendar};
+use anyhow::{Context, Result};
+use chrono::{Datelike, NaiveDate, Utc};
+use cli_table::{format::Justify, Cell, Style, Table};
+use colored::Colorize;
+
+/// An occurrence of a recurring rule that is not logged yet.
+#[derive(Debug, PartialEq)]
+pub struct RecurEntry {
+    pub date: NaiveDate,
+    pub task: String,
+    pub time_spent: u64,
+    pub comment: Option<String>,
+}
+
+pub fn apply(
+    config: &Config,
+    api: &Jira,
+    nager: &Nager,
+    month: &Option<Option<u32>>,
+    year: &Option<i32>,
+    yes: &bool,
+) -> Result<()> {
+    if config.recurring.is_empty() {
+        println!(
+            "No recurring rules yet. Add one with `{}`.",
+            "jtime recur add".green()
+        );
+        return Ok(());
+    }
+
+    let today = Utc::now().date_naive();
+    let (year, month) = selected_month(month.flatten(), *year, today);
+    let mut range = Calendar::days_of_month(year, month)?;
+    if range.from > today {
+        println!("Nothing to apply in the future.");
+        return Ok(());
+    }
+    range.to = range.to.min(today);
+
+    let holidays = match nager.get_holidays_map_for_range(&range) {
+        Ok(holidays) => holidays,
+        Err(err) => {
+            println!("{} {}", "Can't check holidays:".yellow(), err);
+            HolidayMap::new()
+        }
+    };
+    let days_off = DaysOff::load()?;
+    let dates: Vec<NaiveDate> = range
+        .clone()
+        .into_iter()
+        .filter(|d| !holidays.contains_key(&d.format("%Y-%m-%d").to_string()))
+        .filter(|d| days_off.get(*d).is_none())
+        .collect();
+
+    let worklogs = api.fetch_worklogs(range)?;
+    let entries = plan(&config.recurring, &dates, &worklogs, || api.work_units())?;
+    if entries.is_empty() {
+        println!(
+            "{}",
+            "Every occurrence is already logged. Time for coffee! ☕".green()
+        );
+        return Ok(());
+    }
+
+    println!("{}", preview(&entries)?);
+
+    if !confirm(yes)? {
+        println!("Aborted.");
+        return Ok(());
+    }
+
+    let mut operation = Operation::new("recur apply");
+    let result = entries.iter().try_for_each(|entry| -> Result<()> {
+        let worklog_id = api
+            .log_worktime(
+                &entry.task,
+                entry.time_spent,
+                &entry.date,
+                entry.comment.clone(),
+            )
+            .context(format!(
+                "Failed to log time for {}",
+                entry.date.format("%Y-%m-%d")
+            ))?;
+        operation.add(&entry.task, worklog_id, entry.date, entry.time_spent);
+        Ok(())
+    });
+    operation.record()?;
+    result?;
+
+    println!(
+        "{}",
+        "Logged recurring worklogs successfully! Time for coffee! ☕".green()
+    );
+
+    Ok(())
+}
+
+/// Year and month to apply: months after the current one default to last year.
+fn selected_month(month: Option<u32>, year: Option<i32>, today: NaiveDate) -> (i32, u32) {
+    let month = month.unwrap_or_else(|| today.month());
+    let year = year.unwrap_or(if month > today.month() {
+        today.year() - 1
+    } else {
+        today.year()
+    });
+    (year, month)
+}
+
+/// Occurrences of `rules` on `dates` without a matching worklog.
+pub fn plan(
+    rules: &[RecurringRule],
+    dates: &[NaiveDate],
+    worklogs: &WorkLogList,
+    units: impl Fn() -> WorkUnits,
+) -> Result<Vec<RecurEntry>> {
+    let mut entries = Vec::new();
+    for date in dates {
+        let logged = worklogs.get_by_day(*date);
+        for rule in rules {
+            if !rule.occurs_on(*date)? {
+                continue;
+            }
+            let time_spent = parse_duration(&rule.time, &units)?;
+            let already_logged = logged.iter().any(|worklog| {
+                worklog.task == rule.task
+                    && match &rule.comment {
+                        Some(comment) => worklog.comment.as_deref() == Some(comment.as_str()),
+                        None => worklog.time_spent_seconds == time_spent,
+                    }
+            });
+            if !already_logged {
+                entries.push(RecurEntry {
+                    date: *date,
+                    task: rule.task.clone(),
+                    time_spent,
+                    comment: rule.comment.clone(),
+                });
+            }
+        }
+    }
+    Ok(entries)
+}
+
+fn preview(entries: &[RecurEntry]) -> Result<String> {
+    let table = entries
+        .iter()
+        .map(|entry| {
+            vec![
+                entry.date.format("%Y-%m-%d").to_string().cell(),
+                entry.task.green().cell(),
+                format_time(entry.time_spent).cell().justify(Justify::Right),
+                entry.comment.clone().unwrap_or_default().cell(),
+            ]
+        })
+        .collect::<Vec<_>>()
+        .table()
+        .title(vec![
+            "Date".cell(),
+            "Task".cell(),
+            "Time".cell(),
+            "Comment".cell(),
+        ])
+        .bold(true);
+
+    Ok(table.display()?.to_string())
+}
+
+pub fn add(
+    mut config: Config,
+    task: &str,
+    time: &str,
+    comment: &Option<String>,
+    on: &str,
+    every_weeks: &Option<u32>,
+    start: &Option<String>,
+) -> Result<()> {
+    let rule = RecurringRule {
+        task: task.to_string(),
+        time: time.to_string(),
+        comment: comment.clone(),
+        on: on.to_lowercase(),
+        every_weeks: *every_weeks,
+        start: start.as_deref().map(single_date).transpose()?,
+    };
+    parse_time(&rule.time)?;
+    rule.occurs_on(Utc::now().date_naive())?;
+    println!("Added recurring {}", describe(&rule));
+    config.recurring.push(rule);
+    config.save()
+}
+
+pub fn remove(mut config: Config, index: usize) -> Result<()> {
+    if index == 0 || index > config.recurring.len() {
+        anyhow::bail!("Recurring rule {} not found", index);
+    }
+    let rule = config.recurring.remove(index - 1);
+    println!("Removed recurring {}", describe(&rule));
+    config.save()
+}
+
+pub fn list(config: &Config) -> Result<()> {
+    if config.recurring.is_empty() {
+        println!("No recurring rules yet.");
+        return Ok(());
+    }
+    for (index, rule) in config.recurring.iter().enumerate() {
+        println!("{}. {}", index + 1, describe(rule));
+    }
+    Ok(())
+}
+
+/// `TD-1 15m (standup) on weekdays every 2 weeks since 2025-03-07`
+fn describe(rule: &RecurringRule) -> String {
+    let mut text = format!("{} {}", rule.task.green(), rule.time);
+    if let Some(comment) = &rule.comment {
+        text.push_str(&format!(" ({})", comment));
+    }
+    text.push_str(&format!(" on {}", rule.on.yellow()));
+    if let Some(every_weeks) = rule.every_weeks.filter(|w| *w > 1) {
+        text.push_str(&format!(" every {} weeks", every_weeks));
+    }
+    if let Some(start) = rule.start {
+        text.push_str(&format!(" since {}", start.format("%Y-%m-%d")));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{worklog, WorkLog};
+
+    #[test]
+    fn test_plan_skips_logged_occurrences() -> Result<()> {
+        let date = |day: u32| NaiveDate::from_ymd_opt(2025, 3, day).unwrap();
+        let rules = vec![
+            RecurringRule {
+                task: "TD-1".to_string(),
+                time: "15m".to_string(),
+                comment: Some("standup".to_string()),
+                on: "weekdays".to_string(),
+                every_weeks: None,
+                start: None,
+            },
+            RecurringRule {
+                task: "TD-1".to_string(),
+                time: "1h".to_string(),
+                comment: None,
+                on: "fri".to_string(),
+                every_weeks: None,
+                start: None,
+            },
+        ];
+        let worklogs = vec![
+            WorkLog {
+                comment: Some("standup".to_string()),
+                ..worklog(6, "TD-1", 1800)
+            },
+            worklog(7, "TD-1", 3600),
+        ];
+
+        // Thursday 2025-03-06 to Saturday 2025-03-08
+        let entries = plan(
+            &rules,
+            &[date(6), date(7), date(8)],
+            &worklogs,
+            WorkUnits::default,
+        )?;
+
+        assert_eq!(
+            entries,
+            vec![RecurEntry {
+                date: date(7),
+                task: "TD-1".to_string(),
+                time_spent: 900,
+                comment: Some("standup".to_string()),
+            }]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_selected_month() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+        assert_eq!(selected_month(None, None, today), (2025, 3));
+        assert_eq!(selected_month(Some(2), None, today), (2025, 2));
+        assert_eq!(selected_month(Some(11), None, today), (2024, 11));
+        assert_eq!(selected_month(Some(11), Some(2023), today), (2023, 11));
+    }
+}
@@ -0,0 +1,233 @@
+use crate::api::Jira;
+use crate::cli::GroupBy;
+use crate::commands::log::date_range;
+use crate::duration::format_time;
+use crate::models::WorkLogList;
+use anyhow::Result;
+use chrono::{Local, TimeZone};
+use cli_table::{format::Justify, Cell, Style, Table};
+use colored::Colorize;
+use std::collections::{BTreeMap, HashMap};
+
+const NO_EPIC: &str = "(no epic)";
+
+#[derive(Debug, PartialEq)]
+pub struct ReportRow {
+    pub group: String,
+    pub seconds: u64,
+}
+
+pub fn execute(
+    api: &Jira,
+    from: &Option<String>,
+    to: &Option<String>,
+    group_by: &GroupBy,
+    summary: &bool,
+) -> Result<()> {
+    let range = date_range(from, to)?;
+    let worklogs = api.fetch_worklogs(range.clone())?;
+    if worklogs.is_empty() {
+        println!(
+            "No worklogs between {} and {}.",
+            range.from.format("%Y-%m-%d"),
+            range.to.format("%Y-%m-%d")
+        );
+        return Ok(());
+    }
+
+    let mut keys: Vec<String> = worklogs.iter().map(|w| w.task.clone()).collect();
+    keys.sort();
+    keys.dedup();
+    let epics = match group_by {
+        GroupBy::Epic => api.epics(&keys)?,
+        _ => HashMap::new(),
+    };
+    let rows = aggregate(&worklogs, group_by, &epics, &Local);
+
+    // Summaries of the grouped issues or epics, project names for projects
+    let names: HashMap<String, String> = match (summary, group_by) {
+        (false, _) | (true, GroupBy::Day) => HashMap::new(),
+        (true, GroupBy::Project) => api
+            .get_tasks(&keys)?
+            .into_iter()
+            .filter_map(|t| Some((project_key(&t.id), t.project?)))
+            .collect(),
+        (true, _) => {
+            let groups: Vec<String> = rows
+                .iter()
+                .map(|r| r.group.clone())
+                .filter(|g| g != NO_EPIC)
+                .collect();
+            if groups.is_empty() {
+                HashMap::new()
+            } else {
+                api.get_tasks(&groups)?
+                    .into_iter()
+                    .map(|t| (t.id, t.name))
+                    .collect()
+            }
+        }
+    };
+
+    let total: u64 = rows.iter().map(|r| r.seconds).sum();
+    let table = rows
+        .iter()
+        .map(|row| {
+            let mut cells = vec![row.group.clone().green().cell()];
+            if *summary && *group_by != GroupBy::Day {
+                cells.push(names.get(&row.group).cloned().unwrap_or_default().cell());
+            }
+            cells.extend([
+                format_time(row.seconds).cell().justify(Justify::Right),
+                format!("{:.2}", row.seconds as f64 / 3600.0)
+                    .cell()
+                    .justify(Justify::Right),
+                format!("{:.1}%", percent(row.seconds, total))
+                    .cell()
+                    .justify(Justify::Right),
+            ]);
+            cells
+        })
+        .collect::<Vec<_>>()
+        .table()
+        .title(
+            [format!("{:?}", group_by).as_str()]
+                .into_iter()
+                .chain((*summary && *group_by != GroupBy::Day).then_some("Summary"))
+                .chain(["Time", "Hours", "Share"])
+                .map(|title| title.cell())
+                .collect::<Vec<_>>(),
+        )
+        .bold(true);
+
+    println!("{}", table.display()?);
+    println!(
+        "Total from {} to {}: {} ({:.2}h)",
+        range.from.format("%Y-%m-%d"),
+        range.to.format("%Y-%m-%d"),
+        format_time(total).green().bold(),
+        total as f64 / 3600.0
+    );
+    Ok(())
+}
+
+/// Project key of an issue key: `XX` for `XX-12`.
+fn project_key(issue: &str) -> String {
+    issue
+        .rsplit_once('-')
+        .map_or(issue, |(project, _)| project)
+        .to_string()
+}
+
+fn percent(seconds: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        seconds as f64 * 100.0 / total as f64
+    }
+}
+
+/// Time per group, largest first, or in date order by day, with days taken
+/// in the `tz` time zone.
+pub fn aggregate<Tz: TimeZone>(
+    worklogs: &WorkLogList,
+    group_by: &GroupBy,
+    epics: &HashMap<String, String>,
+    tz: &Tz,
+) -> Vec<ReportRow> {
+    let mut groups: BTreeMap<String, u64> = BTreeMap::new();
+    for worklog in worklogs {
+        let group = match group_by {
+            GroupBy::Issue => worklog.task.clone(),
+            GroupBy::Project => project_key(&worklog.task),
+            GroupBy::Epic => epics
+                .get(&worklog.task)
+                .cloned()
+                .unwrap_or_else(|| NO_EPIC.to_string()),
+            GroupBy::Day => worklog
+                .day
+                .with_timezone(tz)
+                .date_naive()
+                .format("%Y-%m-%d")
+                .to_string(),
+        };
+        *groups.entry(group).or_default() += worklog.time_spent_seconds;
+    }
+
+    let mut rows: Vec<ReportRow> = groups
+        .into_iter()
+        .map(|(group, seconds)| ReportRow { group, seconds })
+        .collect();
+    if *group_by != GroupBy::Day {
+        rows.sort_by_key(|row| std::cmp::Reverse(row.seconds));
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{worklog, WorkLog};
+    use chrono::{NaiveDate, Utc};
+
+    #[test]
+    fn test_aggregate() {
+        let worklog = |day: u32, task: &str, minutes: u64| worklog(day, task, minutes * 60);
+        let worklogs = vec![
+            worklog(4, "XX-1", 60),
+            worklog(3, "YY-7", 90),
+            worklog(3, "XX-2", 30),
+            worklog(4, "XX-1", 60),
+        ];
+        let rows = |group_by, epics: &HashMap<String, String>| {
+            aggregate(&worklogs, &group_by, epics, &Utc)
+                .into_iter()
+                .map(|r| (r.group, r.seconds / 60))
+                .collect::<Vec<_>>()
+        };
+        let owned = |rows: &[(&str, u64)]| -> Vec<(String, u64)> {
+            rows.iter().map(|(g, m)| (g.to_string(), *m)).collect()
+        };
+
+        assert_eq!(
+            rows(GroupBy::Issue, &HashMap::new()),
+            owned(&[("XX-1", 120), ("YY-7", 90), ("XX-2", 30)])
+        );
+        assert_eq!(
+            rows(GroupBy::Project, &HashMap::new()),
+            owned(&[("XX", 150), ("YY", 90)])
+        );
+        assert_eq!(
+            rows(GroupBy::Day, &HashMap::new()),
+            owned(&[("2025-03-03", 120), ("2025-03-04", 120)])
+        );
+        let epics = HashMap::from([
+            ("XX-1".to_string(), "XX-100".to_string()),
+            ("XX-2".to_string(), "XX-100".to_string()),
+        ]);
+        assert_eq!(
+            rows(GroupBy::Epic, &epics),
+            owned(&[("XX-100", 150), (NO_EPIC, 90)])
+        );
+        assert_eq!(percent(90, 240), 37.5);
+
+        let late = WorkLog {
+            day: NaiveDate::from_ymd_opt(2025, 3, 3)
+                .unwrap()
+                .and_hms_opt(23, 30, 0)
+                .unwrap()
+                .and_utc(),
+            task: "XX-1".to_string(),
+            time_spent_seconds: 1800,
+            ..Default::default()
+        };
+        let tz = chrono::FixedOffset::east_opt(2 * 3600).unwrap();
+        assert_eq!(
+            aggregate(&vec![late], &GroupBy::Day, &HashMap::new(), &tz),
+            vec![ReportRow {
+                group: "2025-03-04".to_string(),
+                seconds: 1800,
+            }]
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use crate::api::jira::is_network_error;
+use crate::api::Jira;
+use crate::commands::prompt::confirm;
+use crate::duration::format_time;
+use crate::state::journal::Operation;
+use crate::state::outbox::Outbox;
+use anyhow::Result;
+use colored::Colorize;
+
+/// Sends queued worklogs, keeping those that fail in the outbox.
+pub fn execute(api: &Jira, discard: &bool, yes: &bool) -> Result<()> {
+    let outbox = Outbox::load()?;
+    if outbox.0.is_empty() {
+        println!("Nothing to sync.");
+        return Ok(());
+    }
+
+    if *discard {
+        for queued in &outbox.0 {
+            println!(
+                "Discarding {} on {} for task {}",
+                format_time(queued.time_spent).red(),
+                queued.date.format("%Y-%m-%d").to_string().red(),
+                queued.task.red()
+            );
+        }
+        if !confirm(yes)? {
+            println!("Aborted.");
+            return Ok(());
+        }
+        if !api.is_dry_run() {
+            Outbox::default().save()?;
+        }
+        return Ok(());
+    }
+
+    let mut operation = Operation::new("sync");
+    let mut remaining = Outbox::default();
+    let mut queue = outbox.0.into_iter();
+    for queued in queue.by_ref() {
+        let summary = format!(
+            "{} {} {}",
+            queued.date.format("%Y-%m-%d"),
+            queued.task,
+            format_time(queued.time_spent)
+        );
+        match api.log_worktime(
+            &queued.task,
+            queued.time_spent,
+            &queued.date,
+            queued.comment.clone(),
+        ) {
+            Ok(worklog_id) => {
+                operation.add(&queued.task, worklog_id, queued.date, queued.time_spent);
+                println!("{} {}", "✓".green(), summary);
+            }
+            Err(err) if is_network_error(&err) => {
+                println!("{} {}", "Jira is still unreachable:".yellow(), err);
+                remaining.0.push(queued);
+                break;
+            }
+            Err(err) => {
+                println!("{} {} ({})", "✗".red(), summary, err);
+                remaining.0.push(queued);
+            }
+        }
+    }
+    remaining.0.extend(queue);
+    if !api.is_dry_run() {
+        remaining.save()?;
+    }
+    operation.record()?;
+
+    if remaining.0.is_empty() {
+        println!("{}", "Outbox synced successfully!".green());
+    } else {
+        println!(
+            "{} worklog(s) left in the outbox. Run `{}` again or `{}`.",
+            remaining.0.len().to_string().yellow(),
+            "jtime sync".green(),
+            "jtime sync --discard".green()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use crate::config::{Config, Template};
+use crate::duration::parse_time;
+use anyhow::Result;
+use colored::Colorize;
+
+pub fn add(
+    mut config: Config,
+    name: &str,
+    task: &str,
+    time: &Option<String>,
+    comment: &Option<String>,
+    ask_comment: &bool,
+) -> Result<()> {
+    if let Some(time) = time {
+        parse_time(time)?;
+    }
+    let name = name.trim_start_matches('@').to_string();
+    let template = Template {
+        task: task.to_string(),
+        time: time.clone(),
+        comment: comment.clone(),
+        ask_comment: *ask_comment,
+    };
+    let action = if config.templates.contains_key(&name) {
+        "Updated"
+    } else {
+        "Added"
+    };
+    println!("{} template {}", action, describe(&name, &template));
+    config.templates.insert(name, template);
+    config.save()
+}
+
+pub fn remove(mut config: Config, name: &str) -> Result<()> {
+    let name = name.trim_start_matches('@');
+    let template = config
+        .templates
+        .remove(name)
+        .ok_or_else(|| anyhow::anyhow!("Template @{} not found", name))?;
+    println!("Removed template {}", describe(name, &template));
+    config.save()
+}
+
+pub fn list(config: &Config) -> Result<()> {
+    if config.templates.is_empty() {
+        println!(
+            "No templates yet. Add one with `{}`.",
+            "jtime template add".green()
+        );
+        return Ok(());
+    }
+    for (name, template) in &config.templates {
+        println!("{}", describe(name, template));
+    }
+    Ok(())
+}
+
+/// `@retro: TD-12 1h (retro)`
+fn describe(name: &str, template: &Template) -> String {
+    let mut text = format!(
+        "{}: {}",
+        format!("@{}", name).yellow(),
+        template.task.green()
+    );
+    if let Some(time) = &template.time {
+        text.push_str(&format!(" {}", time));
+    }
+    match (&template.comment, template.ask_comment) {
+        (Some(comment), true) => text.push_str(&format!(" ({}, asked)", comment)),
+        (Some(comment), false) => text.push_str(&format!(" ({})", comment)),
+        (None, true) => text.push_str(" (comment asked)"),
+        (None, false) => {}
+    }
+    text
+}
@@ -0,0 +1,124 @@
+use crate::api::{Jira, Nager};
+use crate::cli::LogArgs;
+use crate::commands::log;
+use crate::config::Config;
+use crate::duration::format_time;
+use crate::state::timer::{round_elapsed, Timer};
+use anyhow::Result;
+use chrono::{Local, Utc};
+use colored::Colorize;
+
+pub fn start(task: &str, comment: &Option<String>, dry_run: &bool) -> Result<()> {
+    if let Some(timer) = Timer::load()? {
+        anyhow::bail!(
+            "Timer for {} is already running. Use `{}` or `{}`.",
+            timer.task,
+            "jtime switch".green(),
+            "jtime stop".green()
+        );
+    }
+    begin(task, comment, dry_run)
+}
+
+fn begin(task: &str, comment: &Option<String>, dry_run: &bool) -> Result<()> {
+    let timer = Timer {
+        task: task.to_string(),
+        comment: comment.clone(),
+        started: Utc::now(),
+    };
+    if !dry_run {
+        timer.save()?;
+    }
+    println!("Started timer for {}", timer.task.green());
+    Ok(())
+}
+
+/// Returns whether the timer was stopped (or would be, in dry-run mode).
+pub fn stop(config: &Config, api: &Jira, nager: &Nager, yes: &bool) -> Result<bool> {
+    let timer = match Timer::load()? {
+        Some(timer) => timer,
+        None => {
+            println!("No timer running.");
+            return Ok(true);
+        }
+    };
+
+    let elapsed = round_elapsed(timer.elapsed(Utc::now()), config.timer_rounding()?);
+    if elapsed < 60 {
+        println!("Less than a minute elapsed, nothing to log.");
+        if !api.is_dry_run() {
+            Timer::clear()?;
+        }
+        return Ok(true);
+    }
+
+    println!(
+        "Stopped timer for {} after {}",
+        timer.task.green(),
+        format_time(elapsed).green()
+    );
+
+    let args = LogArgs {
+        task: Some(timer.task.clone()),
+        day: Some(
+            timer
+                .started
+                .with_timezone(&Local)
+                .format("%d-%m-%Y")
+                .to_string(),
+        ),
+        time: Some(format_time(elapsed)),
+        comment: timer.comment.clone(),
+        extra: Vec::new(),
+        yes: *yes,
+        option_day: None,
+        option_time: None,
+        option_comment: None,
+        month: None,
+        year: None,
+        prev_month: false,
+    };
+    let logged = log::execute(config, api, nager, &args)?;
+    if !logged {
+        println!("Timer for {} is still running.", timer.task.yellow());
+    } else if !api.is_dry_run() {
+        Timer::clear()?;
+    }
+    Ok(logged)
+}
+
+pub fn switch(
+    config: &Config,
+    api: &Jira,
+    nager: &Nager,
+    task: &str,
+    comment: &Option<String>,
+    yes: &bool,
+) -> Result<()> {
+    if !stop(config, api, nager, yes)? {
+        anyhow::bail!("Aborted.");
+    }
+    begin(task, comment, &api.is_dry_run())
+}
+
+pub fn status() -> Result<()> {
+    match Timer::load()? {
+        Some(timer) => println!(
+            "Timer for {} running since {} ({}){}",
+            timer.task.green(),
+            timer
+                .started
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+                .blue(),
+            format_time(timer.elapsed(Utc::now())).green(),
+            timer
+                .comment
+                .map(|c| format!(" - {}", c))
+                .unwrap_or_default()
+        ),
+        None => println!("No timer running."),
+    }
+    Ok(())
+}
@@ -0,0 +1,156 @@
+use crate::api::Jira;
+use crate::commands::prompt::confirm;
+use crate::duration::format_time;
+use crate::state::journal::Journal;
+use anyhow::Result;
+use chrono::Local;
+use cli_table::{format::Justify, Cell, Style, Table};
+use colored::Colorize;
+
+pub fn undo(api: &Jira, number: &Option<usize>, yes: &bool) -> Result<()> {
+    let mut journal = Journal::load()?;
+    let Some(index) = journal.find(*number) else {
+        match number {
+            Some(number) => anyhow::bail!("Operation {} not found in `jtime history`", number),
+            None => println!("Nothing to undo."),
+        }
+        return Ok(());
+    };
+
+    let operation = &journal.0[index];
+    if operation.is_undone() {
+        println!("Operation is already undone.");
+        return Ok(());
+    }
+    for worklog in operation.worklogs.iter().filter(|w| !w.deleted) {
+        println!(
+            "Deleting {} logged on {} for task {}",
+            format_time(worklog.time_spent).red(),
+            worklog.date.format("%Y-%m-%d").to_string().red(),
+            worklog.task.red()
+        );
+    }
+    for worklog in operation.replaced.iter().filter(|w| !w.restored) {
+        println!(
+            "Restoring {} on {} for task {}",
+            format_time(worklog.time_spent).green(),
+            worklog.date.format("%Y-%m-%d").to_string().green(),
+            worklog.task.green()
+        );
+    }
+
+    if !confirm(yes)? {
+        println!("Aborted.");
+        return Ok(());
+    }
+
+    let mut failed = 0;
+    for worklog in journal.0[index].worklogs.iter_mut().filter(|w| !w.deleted) {
+        match api.delete_worklog(
+            &worklog.task,
+            &worklog.worklog_id,
+            worklog.date,
+            worklog.time_spent,
+        ) {
+            Ok(_) => worklog.deleted = true,
+            Err(err) => {
+                failed += 1;
+                println!(
+                    "{} {} on {}: {}",
+                    "✗".red(),
+                    worklog.task,
+                    worklog.date.format("%Y-%m-%d"),
+                    err
+                );
+            }
+        }
+    }
+    for worklog in journal.0[index].replaced.iter_mut().filter(|w| !w.restored) {
+        match api.log_worktime(
+            &worklog.task,
+            worklog.time_spent,
+            &worklog.date,
+            worklog.comment.clone(),
+        ) {
+            Ok(_) => worklog.restored = true,
+            Err(err) => {
+                failed += 1;
+                println!(
+                    "{} {} on {}: {}",
+                    "✗".red(),
+                    worklog.task,
+                    worklog.date.format("%Y-%m-%d"),
+                    err
+                );
+            }
+        }
+    }
+    if !api.is_dry_run() {
+        journal.save()?;
+    }
+
+    if failed == 0 {
+        println!("{}", "Operation undone successfully!".green());
+    } else {
+        println!(
+            "{}",
+            format!("{} worklog(s) could not be undone, run undo again", failed).yellow()
+        );
+    }
+    Ok(())
+}
+
+pub fn history() -> Result<()> {
+    let journal = Journal::load()?;
+    if journal.0.is_empty() {
+        println!("No operations yet.");
+        return Ok(());
+    }
+
+    let table = journal
+        .0
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, operation)| {
+            let mut tasks: Vec<&str> = operation.worklogs.iter().map(|w| w.task.as_str()).collect();
+            tasks.sort();
+            tasks.dedup();
+            let status = if operation.is_undone() {
+                "undone".dimmed()
+            } else {
+                "logged".green()
+            };
+            vec![
+                (i + 1).cell().justify(Justify::Right),
+                operation
+                    .at
+                    .with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+                    .cell(),
+                operation.command.clone().cell(),
+                tasks.join(", ").cell(),
+                operation.worklogs.len().cell().justify(Justify::Right),
+                format_time(operation.time_spent())
+                    .cell()
+                    .justify(Justify::Right),
+                status.cell(),
+            ]
+        })
+        .collect::<Vec<_>>()
+        .table()
+        .title(vec![
+            "#".cell(),
+            "When".cell(),
+            "Command".cell(),
+            "Tasks".cell(),
+            "Worklogs".cell(),
+            "Time".cell(),
+            "Status".cell(),
+        ])
+        .bold(true);
+
+    println!("{}", table.display()?);
+    Ok(())
+}
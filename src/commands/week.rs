@@ -1,49 +1,48 @@
 use crate::{
     api::Jira,
     cache::Cache,
+    cli::WeekArgs,
+    commands::log::single_date,
     config::Config,
+    state::{days_off::DaysOff, outbox::Outbox, timer::Timer},
     view::{helper::Helper, Calendar, Render},
 };
 use anyhow::Result;
 
 use crate::api::Nager;
-use chrono::{Datelike, Utc};
-
-pub fn execute(
-    config: &Config,
-    api: &Jira,
-    nager: &Nager,
-    prev: &bool,
-    use_cache: &bool,
-) -> Result<()> {
-    let cache = Cache::new(if *prev { "previous" } else { "current" }.to_string());
-
-    if *use_cache {
+use chrono::{Datelike, Duration, NaiveDate, Utc, Weekday};
+
+pub fn execute(config: &Config, api: &Jira, nager: &Nager, args: &WeekArgs) -> Result<()> {
+    let date = selected_week(args, Utc::now().date_naive())?;
+    let range = Calendar::range_days_for_week(date)?;
+    let cache = Cache::new(format!("week-{}", range.from.format("%G-W%V")));
+
+    if args.cache {
         if let Some(data) = cache.load()? {
             println!("{}", data);
             return Ok(());
         }
     }
 
-    let today = Utc::now().date_naive();
-    let date = if *prev {
-        today - chrono::Duration::days(7)
-    } else {
-        today
-    };
-
-    let range = Calendar::range_days_for_week(date)?;
-    let tasks = api.fetch_worklogs(range.clone())?;
+    let mut tasks = api.fetch_worklogs(range.clone())?;
+    tasks.extend(Timer::load()?.map(|timer| timer.as_worklog(Utc::now())));
+    tasks.extend(Outbox::load()?.as_worklogs());
 
     let actually_works = api.actually_works()?;
+    let holiday_map = nager.get_holidays_map_for_range(&range)?;
+    let days_off = DaysOff::load()?;
+    let schedule = config.schedule()?;
     let output = format!(
-        "{}{}",
+        "{}{}{}",
         Calendar::render(
-            range,
-            tasks,
+            range.clone(),
+            tasks.clone(),
             config.show_weekends,
-            Some(nager.get_all_holidays_map(Utc::now().year().to_string())?)
+            Some(holiday_map.clone()),
+            &days_off,
+            &schedule
         )?,
+        Calendar::balance(range, &tasks, &holiday_map, &days_off, &schedule),
         Calendar::works_on(actually_works)
     );
 
@@ -52,3 +51,71 @@ pub fn execute(
 
     Ok(())
 }
+
+/// A day of the week picked with `--prev`, `--offset`, `--date` or `--week`.
+fn selected_week(args: &WeekArgs, today: NaiveDate) -> Result<NaiveDate> {
+    if let Some(week) = &args.week {
+        return parse_iso_week(week, today.year());
+    }
+    if let Some(day) = &args.date {
+        return single_date(day);
+    }
+    let offset = args.offset.unwrap_or(if args.prev { -1 } else { 0 });
+    Ok(today + Duration::weeks(offset as i64))
+}
+
+/// Monday of an ISO week given as `2025-W11`, or `W11` in `year`.
+fn parse_iso_week(week: &str, year: i32) -> Result<NaiveDate> {
+    let invalid = || anyhow::anyhow!("Invalid week {}, expected e.g. 2025-W11", week);
+    let (week_year, number) = week
+        .trim()
+        .to_uppercase()
+        .split_once('W')
+        .map(|(y, n)| (y.trim_end_matches('-').to_string(), n.to_string()))
+        .ok_or_else(invalid)?;
+    let week_year = match week_year.as_str() {
+        "" => year,
+        y => y.parse().map_err(|_| invalid())?,
+    };
+    let number: u32 = number.parse().map_err(|_| invalid())?;
+    NaiveDate::from_isoywd_opt(week_year, number, Weekday::Mon).ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selected_week() -> Result<()> {
+        let today = NaiveDate::from_ymd_opt(2026, 1, 7).unwrap();
+        let date = |y: i32, m: u32, d: u32| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let args = |prev, offset, week: Option<&str>| WeekArgs {
+            cache: false,
+            prev,
+            offset,
+            date: None,
+            week: week.map(str::to_string),
+        };
+
+        assert_eq!(selected_week(&args(false, None, None), today)?, today);
+        assert_eq!(
+            selected_week(&args(true, None, None), today)?,
+            date(2025, 12, 31)
+        );
+        assert_eq!(
+            selected_week(&args(false, Some(-3), None), today)?,
+            date(2025, 12, 17)
+        );
+        assert_eq!(
+            selected_week(&args(false, None, Some("2025-W11")), today)?,
+            date(2025, 3, 10)
+        );
+        assert_eq!(
+            selected_week(&args(false, None, Some("w2")), today)?,
+            date(2026, 1, 5)
+        );
+        assert!(selected_week(&args(false, None, Some("2025-W54")), today).is_err());
+        assert!(selected_week(&args(false, None, Some("11")), today).is_err());
+        Ok(())
+    }
+}
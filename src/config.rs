@@ -1,23 +1,267 @@
+use crate::api::jira::{Deployment, DEFAULT_TASK_JQL};
+use crate::duration::parse_time;
+use crate::models::Schedule;
 use anyhow::{Context, Result};
+use chrono::{Datelike, NaiveDate};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const DEFAULT_RECENT_WEEKS: u32 = 4;
+const DEFAULT_DAILY_TARGET: &str = "8h";
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Config {
     pub jira_url: String,
     pub jira_token: String,
+    #[serde(default)]
+    pub jira_cloud: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jira_email: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub nager_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub nager_country_code: Option<String>,
     #[serde(default)]
     pub show_weekends: bool,
+    #[serde(default)]
+    pub worklog_backend: WorklogBackendKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tempo_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tempo_token: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tempo_attributes: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_jql: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recent_weeks: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timer_rounding: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub daily_target: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub daily_cap: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workday: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fill_task: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub expected_hours: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub absence_task: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recurring: Vec<RecurringRule>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub templates: BTreeMap<String, Template>,
+}
+
+/// Named overrides selected with `--profile`.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct Profile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_jql: Option<String>,
+}
+
+/// Worklog defaults used with `jtime log @name`.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Template {
+    pub task: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// Ask for the comment, offering `comment` as the default
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub ask_comment: bool,
+}
+
+/// A worklog repeated on a schedule, logged with `jtime recur apply`.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct RecurringRule {
+    pub task: String,
+    pub time: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// `weekdays`, `daily` or a weekday (e.g. `fri`)
+    pub on: String,
+    /// Repeat every N weeks, counted from the week of `start`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub every_weeks: Option<u32>,
+    /// First day the rule applies
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<NaiveDate>,
+}
+
+impl RecurringRule {
+    pub fn occurs_on(&self, day: NaiveDate) -> Result<bool> {
+        if self.start.is_some_and(|start| day < start) {
+            return Ok(false);
+        }
+
+        let weekday = day.weekday();
+        let on_weekday = match self.on.as_str() {
+            "daily" => true,
+            "weekdays" => weekday.num_days_from_monday() < 5,
+            on => {
+                on.parse::<chrono::Weekday>()
+                    .map_err(|_| anyhow::anyhow!("Invalid recurring day: {}", on))?
+                    == weekday
+            }
+        };
+
+        let every_weeks = self.every_weeks.unwrap_or(1).max(1) as i64;
+        let anchor = self
+            .start
+            .unwrap_or(NaiveDate::from_ymd_opt(1970, 1, 5).expect("valid date"));
+        let weeks = (day.week(chrono::Weekday::Mon).first_day()
+            - anchor.week(chrono::Weekday::Mon).first_day())
+        .num_weeks();
+
+        Ok(on_weekday && weeks.rem_euclid(every_weeks) == 0)
+    }
+}
+
+/// Where worklogs are read from.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum WorklogBackendKind {
+    /// Use Actonic when its endpoint responds, native worklogs otherwise
+    #[default]
+    Auto,
+    /// Actonic Timesheet Builder plugin
+    Actonic,
+    /// Jira worklogs found through JQL
+    Native,
+    /// Tempo Timesheets (Cloud REST API)
+    Tempo,
+}
+
+impl std::fmt::Display for WorklogBackendKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            WorklogBackendKind::Auto => "auto",
+            WorklogBackendKind::Actonic => "actonic",
+            WorklogBackendKind::Native => "native",
+            WorklogBackendKind::Tempo => "tempo",
+        };
+        write!(f, "{}", name)
+    }
 }
 
 impl Config {
-    fn file_path() -> Result<String> {
+    pub fn deployment(&self) -> Result<Deployment> {
+        if self.jira_cloud {
+            let email = self
+                .jira_email
+                .clone()
+                .filter(|e| !e.is_empty())
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "jira_email is required for Jira Cloud: jtime config --email <EMAIL>"
+                    )
+                })?;
+            Ok(Deployment::Cloud {
+                email,
+                token: self.jira_token.clone(),
+            })
+        } else {
+            Ok(Deployment::Server {
+                token: self.jira_token.clone(),
+            })
+        }
+    }
+
+    /// JQL of the task picker: the profile's, then the global one, then the default.
+    pub fn task_jql(&self, profile: Option<&str>) -> Result<String> {
+        let profile_jql = match profile {
+            Some(name) => self
+                .profiles
+                .get(name)
+                .ok_or_else(|| anyhow::anyhow!("Profile {} not found", name))?
+                .task_jql
+                .clone(),
+            None => None,
+        };
+        Ok(profile_jql
+            .or_else(|| self.task_jql.clone())
+            .unwrap_or_else(|| DEFAULT_TASK_JQL.to_string()))
+    }
+
+    /// Template by name, with or without the leading `@`.
+    pub fn template(&self, name: &str) -> Result<&Template> {
+        let name = name.trim_start_matches('@');
+        self.templates
+            .get(name)
+            .ok_or_else(|| anyhow::anyhow!("Template @{} not found", name))
+    }
+
+    /// How many weeks of worklogs rank the task picker.
+    pub fn recent_weeks(&self) -> u32 {
+        self.recent_weeks.unwrap_or(DEFAULT_RECENT_WEEKS)
+    }
+
+    /// Directory holding the configuration and local state files.
+    pub fn dir() -> Result<String> {
         let home = std::env::var("HOME").context("Failed to get home directory")?;
-        Ok(format!("{}/.config/jtime/config.json", home))
+        Ok(format!("{}/.config/jtime", home))
+    }
+
+    /// Time expected to be logged on a working day, in seconds.
+    pub fn daily_target(&self) -> Result<u64> {
+        parse_time(self.daily_target.as_deref().unwrap_or(DEFAULT_DAILY_TARGET))
+    }
+
+    /// Step that stopped timers are rounded to, in seconds (0 when unset).
+    pub fn timer_rounding(&self) -> Result<u64> {
+        Ok(self
+            .timer_rounding
+            .as_deref()
+            .map(parse_time)
+            .transpose()?
+            .unwrap_or(0))
+    }
+
+    /// Day total that `log` warns about exceeding, in seconds.
+    pub fn daily_cap(&self) -> Result<Option<u64>> {
+        self.daily_cap.as_deref().map(parse_time).transpose()
+    }
+
+    /// Length of the `d` unit in durations, in seconds. Unset uses Jira's
+    /// time tracking settings.
+    pub fn workday(&self) -> Result<Option<u64>> {
+        self.workday.as_deref().map(parse_time).transpose()
+    }
+
+    /// Expected time per weekday: `daily_target` on Monday to Friday and
+    /// nothing on weekends, overridden by `expected_hours` (e.g. "fri": "4h").
+    pub fn schedule(&self) -> Result<Schedule> {
+        let daily_target = self.daily_target()?;
+        let mut expected = [
+            daily_target,
+            daily_target,
+            daily_target,
+            daily_target,
+            daily_target,
+            0,
+            0,
+        ];
+        for (weekday, time) in &self.expected_hours {
+            let weekday: chrono::Weekday = weekday
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Invalid weekday in expected_hours: {}", weekday))?;
+            expected[weekday.num_days_from_monday() as usize] = parse_time(time)?;
+        }
+        Ok(Schedule {
+            expected,
+            absence_task: self.absence_task.clone(),
+        })
+    }
+
+    fn file_path() -> Result<String> {
+        Ok(format!("{}/config.json", Self::dir()?))
     }
 
     pub fn load() -> Result<Self> {
@@ -52,7 +296,18 @@ impl Config {
         std::io::stdin().read_line(&mut jira_url)?;
         jira_url = jira_url.trim().to_string();
 
-        println!("Enter Jira token (Get from: {}/secure/ViewProfile.jspa?selectedTab=com.atlassian.pats.pats-plugin:jira-user-personal-access-tokens):", &jira_url);
+        let jira_cloud = jira_url.contains(".atlassian.net");
+        let mut jira_email = None;
+        if jira_cloud {
+            println!("Enter your Atlassian account email:");
+            let mut email = String::new();
+            std::io::stdin().read_line(&mut email)?;
+            jira_email = Some(email.trim().to_string());
+
+            println!("Enter Jira API token (Get from: https://id.atlassian.com/manage-profile/security/api-tokens):");
+        } else {
+            println!("Enter Jira token (Get from: {}/secure/ViewProfile.jspa?selectedTab=com.atlassian.pats.pats-plugin:jira-user-personal-access-tokens):", &jira_url);
+        }
         let mut jira_token = String::new();
         std::io::stdin().read_line(&mut jira_token)?;
         jira_token = jira_token.trim().to_string();
@@ -60,9 +315,134 @@ impl Config {
         Ok(Config {
             jira_url,
             jira_token,
+            jira_cloud,
+            jira_email,
             nager_url: None,
             nager_country_code: None,
             show_weekends: false,
+            worklog_backend: WorklogBackendKind::Auto,
+            tempo_url: None,
+            tempo_token: None,
+            tempo_attributes: BTreeMap::new(),
+            task_jql: None,
+            profiles: BTreeMap::new(),
+            recent_weeks: None,
+            timer_rounding: None,
+            daily_target: None,
+            daily_cap: None,
+            workday: None,
+            fill_task: None,
+            expected_hours: BTreeMap::new(),
+            absence_task: None,
+            recurring: Vec::new(),
+            templates: BTreeMap::new(),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Config {
+        serde_json::from_str(r#"{"jira_url": "jira.com", "jira_token": "123"}"#).unwrap()
+    }
+
+    #[test]
+    fn test_task_jql_fallbacks() -> Result<()> {
+        let mut config = config();
+        assert_eq!(config.task_jql(None)?, DEFAULT_TASK_JQL);
+
+        config.task_jql = Some("project = XX".to_string());
+        config.profiles.insert(
+            "support".to_string(),
+            Profile {
+                task_jql: Some("labels = support".to_string()),
+            },
+        );
+        config
+            .profiles
+            .insert("empty".to_string(), Profile::default());
+
+        assert_eq!(config.task_jql(None)?, "project = XX");
+        assert_eq!(config.task_jql(Some("support"))?, "labels = support");
+        assert_eq!(config.task_jql(Some("empty"))?, "project = XX");
+        assert!(config.task_jql(Some("missing")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_schedule_overrides() -> Result<()> {
+        let mut config = config();
+        assert_eq!(
+            config.schedule()?.expected,
+            [28800, 28800, 28800, 28800, 28800, 0, 0]
+        );
+
+        config.daily_target = Some("6h".to_string());
+        config
+            .expected_hours
+            .insert("fri".to_string(), "0h".to_string());
+        config
+            .expected_hours
+            .insert("Saturday".to_string(), "2h".to_string());
+        assert_eq!(
+            config.schedule()?.expected,
+            [21600, 21600, 21600, 21600, 0, 7200, 0]
+        );
+
+        config
+            .expected_hours
+            .insert("someday".to_string(), "2h".to_string());
+        assert!(config.schedule().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_template_lookup() -> Result<()> {
+        let config: Config = serde_json::from_str(
+            r#"{
+                "jira_url": "jira.com",
+                "jira_token": "123",
+                "templates": {
+                    "retro": { "task": "TD-12", "time": "1h", "comment": "retro" }
+                }
+            }"#,
+        )?;
+
+        let retro = config.template("@retro")?;
+        assert_eq!(retro.task, "TD-12");
+        assert_eq!(retro.time.as_deref(), Some("1h"));
+        assert!(!retro.ask_comment);
+        assert_eq!(config.template("retro")?, retro);
+        assert!(config.template("@standup").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_recurring_rule_occurrences() -> Result<()> {
+        let date = |day: u32| NaiveDate::from_ymd_opt(2025, 3, day).unwrap();
+        let standup: RecurringRule = serde_json::from_str(
+            r#"{"task": "TD-1", "time": "15m", "comment": "standup", "on": "weekdays"}"#,
+        )?;
+        let retro: RecurringRule = serde_json::from_str(
+            r#"{"task": "TD-1", "time": "1h", "on": "fri", "every_weeks": 2, "start": "2025-03-07"}"#,
+        )?;
+
+        // 2025-03-07 is a Friday
+        assert!(standup.occurs_on(date(7))?);
+        assert!(!standup.occurs_on(date(8))?);
+        assert!(!retro.occurs_on(date(6))?);
+        assert!(retro.occurs_on(date(7))?);
+        assert!(!retro.occurs_on(date(14))?);
+        assert!(retro.occurs_on(date(21))?);
+        assert!(!retro.occurs_on(NaiveDate::from_ymd_opt(2025, 2, 21).unwrap())?);
+
+        let invalid = RecurringRule {
+            on: "someday".to_string(),
+            ..standup
+        };
+        assert!(invalid.occurs_on(date(7)).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,243 @@
+//! Parsing and formatting of worklog durations.
+use anyhow::Result;
+
+/// Lengths of the `d` and `w` duration units, in seconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WorkUnits {
+    pub day: u64,
+    pub week: u64,
+}
+
+impl Default for WorkUnits {
+    fn default() -> Self {
+        WorkUnits {
+            day: 8 * 3600,
+            week: 5 * 8 * 3600,
+        }
+    }
+}
+
+/// Whether the duration is the `rest` keyword: whatever is left to the
+/// expected time of the day.
+pub fn is_rest(time_str: &str) -> bool {
+    time_str.trim().eq_ignore_ascii_case("rest")
+}
+
+pub fn parse_time(time_str: &str) -> Result<u64> {
+    parse_duration(time_str, WorkUnits::default)
+}
+
+/// Parses `1h30m`, `1.5h`, `1h 30m`, `1:30`, `2d` or `1w`. Work units are only
+/// looked up when the duration uses days or weeks.
+pub fn parse_duration(time_str: &str, units: impl Fn() -> WorkUnits) -> Result<u64> {
+    let chars: Vec<char> = time_str.chars().collect();
+    let invalid = |pos: usize, message: String| {
+        anyhow::anyhow!(
+            "{} in '{}'\n  {}\n  {}^",
+            message,
+            time_str,
+            time_str,
+            " ".repeat(pos)
+        )
+    };
+
+    if is_rest(time_str) {
+        anyhow::bail!("'rest' is only supported by `jtime log`");
+    }
+    if time_str.trim().is_empty() {
+        anyhow::bail!("No time provided");
+    }
+
+    if let Some((hours, minutes)) = time_str.trim().split_once(':') {
+        let offset = time_str.len() - time_str.trim_start().len();
+        let colon = offset + hours.chars().count();
+        if let Some(pos) = hours.chars().position(|c| !c.is_ascii_digit()) {
+            return Err(invalid(
+                offset + pos,
+                "Expected hours before ':'".to_string(),
+            ));
+        }
+        if let Some(pos) = minutes.chars().position(|c| !c.is_ascii_digit()) {
+            return Err(invalid(
+                colon + 1 + pos,
+                "Expected minutes after ':'".to_string(),
+            ));
+        }
+        let (Ok(hours), Ok(minutes)) = (hours.parse::<u64>(), minutes.parse::<u64>()) else {
+            return Err(invalid(colon, "Expected hours:minutes".to_string()));
+        };
+        if minutes >= 60 {
+            return Err(invalid(colon + 1, "Minutes must be below 60".to_string()));
+        }
+        return Ok(hours * 3600 + minutes * 60);
+    }
+
+    let mut seconds = 0.0;
+    let mut pos = 0;
+    while pos < chars.len() {
+        if chars[pos].is_whitespace() {
+            pos += 1;
+            continue;
+        }
+
+        let start = pos;
+        while pos < chars.len() && (chars[pos].is_ascii_digit() || chars[pos] == '.') {
+            pos += 1;
+        }
+        if start == pos {
+            return Err(invalid(
+                pos,
+                format!("No number provided for time unit '{}'", chars[pos]),
+            ));
+        }
+        let number: String = chars[start..pos].iter().collect();
+        let number: f64 = number
+            .parse()
+            .map_err(|_| invalid(start, format!("Invalid number '{}'", number)))?;
+
+        while pos < chars.len() && chars[pos].is_whitespace() {
+            pos += 1;
+        }
+        let Some(unit) = chars.get(pos) else {
+            return Err(invalid(
+                pos,
+                format!("Incomplete time format, missing unit for '{}'", number),
+            ));
+        };
+        seconds += number
+            * match unit {
+                'w' => units().week as f64,
+                'd' => units().day as f64,
+                'h' => 3600.0,
+                'm' => 60.0,
+                _ => return Err(invalid(pos, format!("Invalid time unit: '{}'", unit))),
+            };
+        pos += 1;
+    }
+
+    Ok(seconds.round() as u64)
+}
+
+/// Splits a duration into `parts` whole-minute chunks that add up exactly,
+/// giving the leftover minutes to the first parts.
+pub fn split_evenly(seconds: u64, parts: usize) -> Vec<u64> {
+    if parts == 0 {
+        return Vec::new();
+    }
+    let minutes = seconds / 60;
+    let (base, rest) = (minutes / parts as u64, minutes % parts as u64);
+    (0..parts as u64)
+        .map(|i| (base + u64::from(i < rest)) * 60)
+        .enumerate()
+        .map(|(i, part)| if i == 0 { part + seconds % 60 } else { part })
+        .collect()
+}
+
+pub fn format_time(seconds: u64) -> String {
+    let hours = seconds / 3600;
+    let minutes = (seconds % 3600) / 60;
+    match (hours, minutes) {
+        (0, m) => format!("{}m", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h{}m", h, m),
+    }
+}
+
+/// Signed duration such as `+1h30m` or `-2h`.
+pub fn format_delta(seconds: i64) -> String {
+    let sign = if seconds < 0 { "-" } else { "+" };
+    format!("{}{}", sign, format_time(seconds.unsigned_abs()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_time_hours_only() -> Result<()> {
+        // 2 hours = 7200 seconds
+        let seconds = parse_time("2h")?;
+        assert_eq!(seconds, 7200);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_time_minutes_only() -> Result<()> {
+        // 45 minutes = 2700 seconds
+        let seconds = parse_time("45m")?;
+        assert_eq!(seconds, 2700);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_time_hour_and_minutes() -> Result<()> {
+        // 1 hour and 30 minutes = 3600 + 1800 = 5400 seconds
+        let seconds = parse_time("1h30m")?;
+        assert_eq!(seconds, 5400);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_time_invalid_unit() {
+        // Using an invalid unit 's'
+        let result = parse_time("10s");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_time_incomplete_format() {
+        // Trailing number without unit
+        let result = parse_time("15");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_time_grammar() -> Result<()> {
+        assert_eq!(parse_time("1.5h")?, 5400);
+        assert_eq!(parse_time("0.25h")?, 900);
+        assert_eq!(parse_time("1h 30m")?, 5400);
+        assert_eq!(parse_time(" 2 h ")?, 7200);
+        assert_eq!(parse_time("1:30")?, 5400);
+        assert_eq!(parse_time("0:05")?, 300);
+        assert_eq!(parse_time("1d")?, 8 * 3600);
+        assert_eq!(parse_time("1w 0.5d")?, 44 * 3600);
+
+        let units = || WorkUnits {
+            day: 7 * 3600 + 1800,
+            week: 4 * (7 * 3600 + 1800),
+        };
+        assert_eq!(parse_duration("2d", units)?, 15 * 3600);
+        assert_eq!(parse_duration("1w", units)?, 30 * 3600);
+
+        let err = parse_time("1h 3x").unwrap_err().to_string();
+        assert!(err.contains("'x'"));
+        assert!(err.ends_with("\n  1h 3x\n      ^"));
+        assert!(parse_time("1:75").is_err());
+        assert!(parse_time("1.2.3h").is_err());
+        assert!(parse_time("rest").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_evenly() {
+        assert_eq!(split_evenly(8 * 3600, 3), vec![9600, 9600, 9600]);
+        assert_eq!(split_evenly(3600 + 120, 3), vec![1260, 1260, 1200]);
+        assert_eq!(split_evenly(90, 2), vec![90, 0]);
+        assert_eq!(split_evenly(3600, 0), Vec::<u64>::new());
+        assert_eq!(
+            split_evenly(7 * 3600 + 17, 4).iter().sum::<u64>(),
+            7 * 3600 + 17
+        );
+    }
+
+    #[test]
+    fn test_format_time_roundtrip() -> Result<()> {
+        assert_eq!(format_time(5400), "1h30m");
+        assert_eq!(format_time(7200), "2h");
+        assert_eq!(format_time(2700), "45m");
+        assert_eq!(parse_time(&format_time(5400))?, 5400);
+        assert_eq!(format_delta(-5400), "-1h30m");
+        assert_eq!(format_delta(3600), "+1h");
+        Ok(())
+    }
+}
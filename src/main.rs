@@ -4,7 +4,9 @@ mod cache;
 mod cli;
 mod commands;
 mod config;
+mod duration;
 mod models;
+mod state;
 mod view;
 use anyhow::Result;
 use clap::Parser;
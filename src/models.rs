@@ -63,18 +63,67 @@ impl IntoIterator for DateRange {
 pub struct Task {
     pub id: String,
     pub name: String,
+    pub status: Option<String>,
+    pub project: Option<String>,
 }
 
-#[derive(Clone, Debug)]
+impl Task {
+    /// Picker label: `(XX-1) Summary [Status · Project]`
+    pub fn label(&self) -> String {
+        let details = [&self.status, &self.project]
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect::<Vec<_>>();
+        if details.is_empty() {
+            format!("({}) {}", self.id, self.name)
+        } else {
+            format!("({}) {} [{}]", self.id, self.name, details.join(" · "))
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
 pub struct WorkLog {
+    pub id: String,
+    pub issue_id: String,
     pub day: DateTime<Utc>,
     pub task: String,
     pub time_spent: String,
+    pub time_spent_seconds: u64,
+    pub comment: Option<String>,
+    pub state: WorkLogState,
+}
+
+/// Whether a worklog exists in Jira or is still pending locally.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum WorkLogState {
+    #[default]
+    Logged,
+    /// Elapsed time of the running `jtime start` timer
+    Running,
+    /// Row of `jtime import` not posted yet
+    Pending,
+    /// Waiting in the outbox for `jtime sync`
+    Queued,
+}
+
+impl WorkLog {
+    /// Issue id when known, otherwise the issue key. Both are accepted by Jira.
+    pub fn issue(&self) -> &str {
+        if self.issue_id.is_empty() {
+            &self.task
+        } else {
+            &self.issue_id
+        }
+    }
 }
 
 pub type WorkLogList = Vec<WorkLog>;
 pub trait WorkLogListExt {
     fn get_by_day(&self, day: NaiveDate) -> WorkLogList;
+    fn logged_on(&self, day: NaiveDate) -> u64;
+    fn counted_on(&self, day: NaiveDate) -> u64;
 }
 
 impl WorkLogListExt for WorkLogList {
@@ -84,7 +133,56 @@ impl WorkLogListExt for WorkLogList {
             .cloned()
             .collect()
     }
+
+    /// Seconds already logged in Jira on the day.
+    fn logged_on(&self, day: NaiveDate) -> u64 {
+        self.get_by_day(day)
+            .iter()
+            .filter(|worklog| worklog.state == WorkLogState::Logged)
+            .map(|worklog| worklog.time_spent_seconds)
+            .sum()
+    }
+
+    /// Seconds counted in the calendar totals: logged in Jira or about to be
+    /// imported.
+    fn counted_on(&self, day: NaiveDate) -> u64 {
+        self.get_by_day(day)
+            .iter()
+            .filter(|worklog| matches!(worklog.state, WorkLogState::Logged | WorkLogState::Pending))
+            .map(|worklog| worklog.time_spent_seconds)
+            .sum()
+    }
 }
+
+/// Expected working time per weekday in seconds, Monday first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Schedule {
+    pub expected: [u64; 7],
+    /// Task days off are logged to, whose time still counts as expected
+    pub absence_task: Option<String>,
+}
+
+impl Schedule {
+    pub fn expected_on(&self, day: NaiveDate) -> u64 {
+        self.expected[chrono::Datelike::weekday(&day).num_days_from_monday() as usize]
+    }
+}
+
+/// Worklog at 08:00 UTC on a day of March 2025, for tests.
+#[cfg(test)]
+pub fn worklog(day: u32, task: &str, seconds: u64) -> WorkLog {
+    WorkLog {
+        day: NaiveDate::from_ymd_opt(2025, 3, day)
+            .unwrap()
+            .and_hms_opt(8, 0, 0)
+            .unwrap()
+            .and_utc(),
+        task: task.to_string(),
+        time_spent_seconds: seconds,
+        ..Default::default()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -121,6 +219,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_task_label() {
+        let mut task = Task {
+            id: "XX-1".to_string(),
+            name: "Fix login".to_string(),
+            status: Some("In Progress".to_string()),
+            project: Some("Portal".to_string()),
+        };
+        assert_eq!(task.label(), "(XX-1) Fix login [In Progress · Portal]");
+
+        task.status = None;
+        task.project = None;
+        assert_eq!(task.label(), "(XX-1) Fix login");
+    }
+
     #[test]
     fn test_worklog_get_by_day() {
         let day1 = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
@@ -133,6 +246,7 @@ mod tests {
             ),
             task: "Task1".to_string(),
             time_spent: "2h".to_string(),
+            ..Default::default()
         };
 
         let log2 = WorkLog {
@@ -142,6 +256,7 @@ mod tests {
             ),
             task: "Task2".to_string(),
             time_spent: "3h".to_string(),
+            ..Default::default()
         };
 
         let log3 = WorkLog {
@@ -151,6 +266,7 @@ mod tests {
             ),
             task: "Task3".to_string(),
             time_spent: "4h".to_string(),
+            ..Default::default()
         };
 
         let logs = vec![log1, log2, log3];
@@ -167,4 +283,21 @@ mod tests {
         let day3_logs = logs.get_by_day(NaiveDate::from_ymd_opt(2023, 1, 3).unwrap());
         assert_eq!(day3_logs.len(), 0);
     }
+
+    #[test]
+    fn test_worklog_logged_and_counted_on() {
+        let day = NaiveDate::from_ymd_opt(2025, 3, 3).unwrap();
+        let with_state = |hours: u64, state: WorkLogState| WorkLog {
+            state,
+            ..worklog(3, "XX-1", hours * 3600)
+        };
+        let logs = vec![
+            with_state(2, WorkLogState::Logged),
+            with_state(3, WorkLogState::Pending),
+            with_state(4, WorkLogState::Running),
+        ];
+
+        assert_eq!(logs.logged_on(day), 2 * 3600);
+        assert_eq!(logs.counted_on(day), 5 * 3600);
+    }
 }
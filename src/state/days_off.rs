@@ -0,0 +1,95 @@
+use crate::models::DateRange;
+use anyhow::Result;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const STATE_FILE: &str = "days_off";
+
+/// Why a working day is taken off.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum DayOffKind {
+    #[default]
+    Vacation,
+    Sick,
+    Other,
+}
+
+impl std::fmt::Display for DayOffKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            DayOffKind::Vacation => "Vacation",
+            DayOffKind::Sick => "Sick leave",
+            DayOffKind::Other => "Day off",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// Days off added with `jtime off add`, keyed by date.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct DaysOff(BTreeMap<NaiveDate, DayOffKind>);
+
+impl DaysOff {
+    pub fn load() -> Result<DaysOff> {
+        super::load(STATE_FILE)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        super::save(STATE_FILE, self)
+    }
+
+    pub fn add(&mut self, date: NaiveDate, kind: DayOffKind) {
+        self.0.insert(date, kind);
+    }
+
+    pub fn remove(&mut self, date: NaiveDate) -> Option<DayOffKind> {
+        self.0.remove(&date)
+    }
+
+    pub fn get(&self, date: NaiveDate) -> Option<DayOffKind> {
+        self.0.get(&date).copied()
+    }
+
+    pub fn in_range(&self, range: &DateRange) -> Vec<(NaiveDate, DayOffKind)> {
+        self.0
+            .range(range.from..=range.to)
+            .map(|(date, kind)| (*date, *kind))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_off_roundtrip() {
+        let date = |day: u32| NaiveDate::from_ymd_opt(2025, 12, day).unwrap();
+        let mut days_off = DaysOff::default();
+        days_off.add(date(15), DayOffKind::Vacation);
+        days_off.add(date(16), DayOffKind::Sick);
+        days_off.add(date(31), DayOffKind::Other);
+
+        let json = serde_json::to_string(&days_off).unwrap();
+        assert_eq!(
+            json,
+            r#"{"2025-12-15":"vacation","2025-12-16":"sick","2025-12-31":"other"}"#
+        );
+        let days_off: DaysOff = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(days_off.get(date(16)), Some(DayOffKind::Sick));
+        assert_eq!(days_off.get(date(17)), None);
+        assert_eq!(
+            days_off.in_range(&DateRange {
+                from: date(1),
+                to: date(16),
+            }),
+            vec![
+                (date(15), DayOffKind::Vacation),
+                (date(16), DayOffKind::Sick)
+            ]
+        );
+    }
+}
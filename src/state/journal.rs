@@ -0,0 +1,167 @@
+use crate::models::WorkLog;
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+const STATE_FILE: &str = "journal";
+/// Older operations are dropped from the journal.
+const MAX_OPERATIONS: usize = 50;
+
+/// Worklogs posted by one command, kept so they can be undone.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Operation {
+    pub command: String,
+    pub at: DateTime<Utc>,
+    pub worklogs: Vec<JournalEntry>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub replaced: Vec<ReplacedEntry>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct JournalEntry {
+    pub task: String,
+    pub worklog_id: String,
+    pub date: NaiveDate,
+    pub time_spent: u64,
+    #[serde(default)]
+    pub deleted: bool,
+}
+
+/// Worklog the operation deleted to replace it, logged again on undo.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ReplacedEntry {
+    pub task: String,
+    pub date: NaiveDate,
+    pub time_spent: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(default)]
+    pub restored: bool,
+}
+
+impl Operation {
+    pub fn new(command: &str) -> Self {
+        Operation {
+            command: command.to_string(),
+            at: Utc::now(),
+            worklogs: Vec::new(),
+            replaced: Vec::new(),
+        }
+    }
+
+    pub fn add(&mut self, task: &str, worklog_id: String, date: NaiveDate, time_spent: u64) {
+        self.worklogs.push(JournalEntry {
+            task: task.to_string(),
+            worklog_id,
+            date,
+            time_spent,
+            deleted: false,
+        });
+    }
+
+    pub fn replace(&mut self, worklog: &WorkLog) {
+        self.replaced.push(ReplacedEntry {
+            task: worklog.task.clone(),
+            date: worklog.day.date_naive(),
+            time_spent: worklog.time_spent_seconds,
+            comment: worklog.comment.clone(),
+            restored: false,
+        });
+    }
+
+    /// Appends the operation to the journal when it created or replaced any
+    /// worklog. Worklogs without an id (dry run) can't be undone and are left out.
+    pub fn record(mut self) -> Result<()> {
+        self.worklogs.retain(|w| !w.worklog_id.is_empty());
+        if self.worklogs.is_empty() && self.replaced.is_empty() {
+            return Ok(());
+        }
+        let mut journal = Journal::load()?;
+        journal.push(self);
+        journal.save()
+    }
+
+    pub fn is_undone(&self) -> bool {
+        self.worklogs.iter().all(|w| w.deleted) && self.replaced.iter().all(|w| w.restored)
+    }
+
+    pub fn time_spent(&self) -> u64 {
+        self.worklogs.iter().map(|w| w.time_spent).sum()
+    }
+}
+
+/// Past operations, oldest first.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct Journal(pub Vec<Operation>);
+
+impl Journal {
+    pub fn load() -> Result<Journal> {
+        super::load(STATE_FILE)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        super::save(STATE_FILE, self)
+    }
+
+    fn push(&mut self, operation: Operation) {
+        self.0.push(operation);
+        if self.0.len() > MAX_OPERATIONS {
+            self.0.drain(..self.0.len() - MAX_OPERATIONS);
+        }
+    }
+
+    /// Index of the operation numbered as in `jtime history` (1 is the most
+    /// recent), or of the most recent one not undone yet.
+    pub fn find(&self, number: Option<usize>) -> Option<usize> {
+        match number {
+            Some(number) => (1..=self.0.len())
+                .contains(&number)
+                .then(|| self.0.len() - number),
+            None => self.0.iter().rposition(|op| !op.is_undone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::worklog;
+
+    #[test]
+    fn test_journal_find_and_trim() {
+        let date = NaiveDate::from_ymd_opt(2025, 3, 3).unwrap();
+        let mut journal = Journal::default();
+        for i in 0..MAX_OPERATIONS + 2 {
+            let mut operation = Operation::new("log");
+            operation.add("XX-1", i.to_string(), date, 3600);
+            journal.push(operation);
+        }
+        assert_eq!(journal.0.len(), MAX_OPERATIONS);
+        assert_eq!(journal.0[0].worklogs[0].worklog_id, "2");
+
+        journal.0.last_mut().unwrap().worklogs[0].deleted = true;
+        assert_eq!(journal.find(None), Some(MAX_OPERATIONS - 2));
+        assert_eq!(journal.find(Some(1)), Some(MAX_OPERATIONS - 1));
+        assert_eq!(journal.find(Some(MAX_OPERATIONS)), Some(0));
+        assert_eq!(journal.find(Some(0)), None);
+        assert_eq!(journal.find(Some(MAX_OPERATIONS + 1)), None);
+    }
+
+    #[test]
+    fn test_operation_with_replaced_worklogs() {
+        let date = NaiveDate::from_ymd_opt(2025, 3, 3).unwrap();
+        let mut operation = Operation::new("log");
+        operation.add("XX-1", "10".to_string(), date, 3600);
+        operation.replace(&WorkLog {
+            comment: Some("review".to_string()),
+            ..worklog(3, "XX-2", 1800)
+        });
+        assert_eq!(operation.replaced[0].date, date);
+        assert_eq!(operation.replaced[0].comment.as_deref(), Some("review"));
+
+        operation.worklogs[0].deleted = true;
+        assert!(!operation.is_undone());
+        operation.replaced[0].restored = true;
+        assert!(operation.is_undone());
+    }
+}
@@ -0,0 +1,33 @@
+//! Local state files kept next to the configuration.
+pub mod days_off;
+pub mod journal;
+pub mod outbox;
+pub mod timer;
+
+use crate::config::Config;
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+fn file_path(name: &str) -> Result<String> {
+    Ok(format!("{}/{}.json", Config::dir()?, name))
+}
+
+/// Reads a state file, falling back to the default value when it does not exist yet.
+pub fn load<T: DeserializeOwned + Default>(name: &str) -> Result<T> {
+    let path = file_path(name)?;
+    match std::fs::File::open(&path) {
+        Ok(file) => serde_json::from_reader(file).context(format!("Failed to parse {}", path)),
+        Err(_) => Ok(T::default()),
+    }
+}
+
+pub fn save<T: Serialize>(name: &str, value: &T) -> Result<()> {
+    let path = file_path(name)?;
+    if let Some(parent) = std::path::Path::new(&path).parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file = std::fs::File::create(&path)?;
+    serde_json::to_writer_pretty(file, value)?;
+    Ok(())
+}
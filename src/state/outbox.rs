@@ -0,0 +1,89 @@
+use crate::duration::format_time;
+use crate::models::{WorkLog, WorkLogList, WorkLogState};
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+const STATE_FILE: &str = "outbox";
+
+/// A worklog kept locally while Jira is unreachable, sent with `jtime sync`.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct QueuedWorklog {
+    pub task: String,
+    pub time_spent: u64,
+    pub date: NaiveDate,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    pub queued_at: DateTime<Utc>,
+}
+
+impl QueuedWorklog {
+    /// The queued worklog as shown in the calendar.
+    pub fn as_worklog(&self) -> WorkLog {
+        WorkLog {
+            day: self
+                .date
+                .and_hms_opt(8, 0, 0)
+                .expect("valid time")
+                .and_utc(),
+            task: self.task.clone(),
+            time_spent: format_time(self.time_spent),
+            time_spent_seconds: self.time_spent,
+            comment: self.comment.clone(),
+            state: WorkLogState::Queued,
+            ..Default::default()
+        }
+    }
+}
+
+/// Queued worklogs, oldest first.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct Outbox(pub Vec<QueuedWorklog>);
+
+impl Outbox {
+    pub fn load() -> Result<Outbox> {
+        super::load(STATE_FILE)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        super::save(STATE_FILE, self)
+    }
+
+    pub fn push(&mut self, task: &str, time_spent: u64, date: NaiveDate, comment: Option<String>) {
+        self.0.push(QueuedWorklog {
+            task: task.to_string(),
+            time_spent,
+            date,
+            comment,
+            queued_at: Utc::now(),
+        });
+    }
+
+    pub fn as_worklogs(&self) -> WorkLogList {
+        self.0.iter().map(|queued| queued.as_worklog()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outbox_as_worklogs() {
+        let mut outbox = Outbox::default();
+        outbox.push(
+            "XX-1",
+            5400,
+            NaiveDate::from_ymd_opt(2025, 3, 5).unwrap(),
+            Some("review".to_string()),
+        );
+        let outbox: Outbox =
+            serde_json::from_str(&serde_json::to_string(&outbox).unwrap()).unwrap();
+
+        let worklogs = outbox.as_worklogs();
+        assert_eq!(worklogs.len(), 1);
+        assert_eq!(worklogs[0].state, WorkLogState::Queued);
+        assert_eq!(worklogs[0].time_spent, "1h30m");
+        assert_eq!(worklogs[0].day.format("%Y-%m-%d").to_string(), "2025-03-05");
+    }
+}
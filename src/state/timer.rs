@@ -0,0 +1,90 @@
+use crate::duration::format_time;
+use crate::models::{WorkLog, WorkLogState};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+const STATE_FILE: &str = "timer";
+
+/// A running `jtime start` timer.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Timer {
+    pub task: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    pub started: DateTime<Utc>,
+}
+
+impl Timer {
+    pub fn load() -> Result<Option<Timer>> {
+        super::load(STATE_FILE)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        super::save(STATE_FILE, &Some(self))
+    }
+
+    pub fn clear() -> Result<()> {
+        super::save::<Option<Timer>>(STATE_FILE, &None)
+    }
+
+    pub fn elapsed(&self, now: DateTime<Utc>) -> u64 {
+        (now - self.started).num_seconds().max(0) as u64
+    }
+
+    /// The timer shown as a pending worklog in the calendar.
+    pub fn as_worklog(&self, now: DateTime<Utc>) -> WorkLog {
+        let elapsed = self.elapsed(now);
+        WorkLog {
+            day: self.started,
+            task: self.task.clone(),
+            time_spent: format_time(elapsed),
+            time_spent_seconds: elapsed,
+            comment: self.comment.clone(),
+            state: WorkLogState::Running,
+            ..Default::default()
+        }
+    }
+}
+
+/// Rounds to the nearest multiple of `unit` seconds, but never down to zero.
+pub fn round_elapsed(seconds: u64, unit: u64) -> u64 {
+    if unit == 0 || seconds == 0 {
+        return seconds;
+    }
+    let rounded = (seconds + unit / 2) / unit * unit;
+    rounded.max(unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_elapsed() {
+        assert_eq!(round_elapsed(0, 900), 0);
+        assert_eq!(round_elapsed(60, 900), 900);
+        assert_eq!(round_elapsed(1300, 900), 900);
+        assert_eq!(round_elapsed(1400, 900), 1800);
+        assert_eq!(round_elapsed(1234, 0), 1234);
+    }
+
+    #[test]
+    fn test_timer_elapsed() {
+        let started = DateTime::parse_from_rfc3339("2025-03-05T08:00:00Z")
+            .unwrap()
+            .to_utc();
+        let timer = Timer {
+            task: "XX-1".to_string(),
+            comment: None,
+            started,
+        };
+        let now = started + chrono::Duration::minutes(90);
+        assert_eq!(timer.elapsed(now), 5400);
+        assert_eq!(timer.elapsed(started - chrono::Duration::minutes(1)), 0);
+
+        let worklog = timer.as_worklog(now);
+        assert_eq!(worklog.state, WorkLogState::Running);
+        assert_eq!(worklog.time_spent, "1h30m");
+    }
+}
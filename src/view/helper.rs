@@ -4,12 +4,13 @@ use anyhow::{anyhow, Result};
 use chrono::{Datelike, Duration, NaiveDate};
 
 pub trait Helper {
+    fn days_of_month(year: i32, month: u32) -> Result<DateRange>;
     fn range_days_for_month(year: i32, month: u32) -> Result<DateRange>;
     fn range_days_for_week(date: NaiveDate) -> Result<DateRange>;
 }
 
 impl Helper for Calendar {
-    fn range_days_for_month(year: i32, month: u32) -> Result<DateRange> {
+    fn days_of_month(year: i32, month: u32) -> Result<DateRange> {
         if !(1..=12).contains(&month) {
             return Err(anyhow!("Invalid month"));
         }
@@ -29,6 +30,18 @@ impl Helper for Calendar {
                 .ok_or_else(|| anyhow!("Failed to calculate last day of month"))?
         };
 
+        Ok(DateRange {
+            from: first_day,
+            to: last_day,
+        })
+    }
+
+    fn range_days_for_month(year: i32, month: u32) -> Result<DateRange> {
+        let DateRange {
+            from: first_day,
+            to: last_day,
+        } = Self::days_of_month(year, month)?;
+
         let monday = first_day
             .checked_sub_signed(Duration::days(
                 first_day.weekday().num_days_from_monday() as i64
@@ -37,7 +50,7 @@ impl Helper for Calendar {
 
         let last_sunday = last_day
             .checked_add_signed(Duration::days(
-                7 - last_day.weekday().num_days_from_sunday() as i64,
+                6 - last_day.weekday().num_days_from_monday() as i64,
             ))
             .ok_or_else(|| anyhow!("Failed to calculate Sunday date"))?;
 
@@ -53,7 +66,7 @@ impl Helper for Calendar {
 
         let sunday = date
             .checked_add_signed(Duration::days(
-                7 - date.weekday().num_days_from_sunday() as i64,
+                6 - date.weekday().num_days_from_monday() as i64,
             ))
             .ok_or_else(|| anyhow!("Failed to calculate Sunday date"))?;
 
@@ -65,6 +78,7 @@ impl Helper for Calendar {
 }
 
 #[cfg(test)]
+#[allow(clippy::zero_prefixed_literal)]
 mod tests {
     use super::Helper;
     use super::*;
@@ -95,6 +109,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_range_days_for_month_ending_on_sunday() {
+        let result = Calendar::range_days_for_month(2025, 8).unwrap();
+        assert_eq!(
+            result,
+            DateRange {
+                from: NaiveDate::from_ymd_opt(2025, 7, 28).unwrap(),
+                to: NaiveDate::from_ymd_opt(2025, 8, 31).unwrap(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_days_of_month() {
+        let result = Calendar::days_of_month(2024, 2).unwrap();
+        assert_eq!(
+            result,
+            DateRange {
+                from: NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(),
+                to: NaiveDate::from_ymd_opt(2024, 2, 29).unwrap(),
+            }
+        );
+        assert!(Calendar::days_of_month(2024, 13).is_err());
+    }
+
     #[test]
     fn test_range_days_for_week() {
         // Test for 2025-03-03 (Wednesday)
@@ -107,5 +146,8 @@ mod tests {
                 to: NaiveDate::from_ymd_opt(2025, 3, 9).unwrap(),
             }
         );
+        let sunday =
+            Calendar::range_days_for_week(NaiveDate::from_ymd_opt(2025, 3, 9).unwrap()).unwrap();
+        assert_eq!(sunday, result);
     }
 }
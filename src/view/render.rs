@@ -1,6 +1,8 @@
 use super::Calendar;
 use crate::api::nager::HolidayMap;
-use crate::models::{DateRange, Task, WorkLogList, WorkLogListExt};
+use crate::duration::{format_delta, format_time};
+use crate::models::{DateRange, Schedule, Task, WorkLogList, WorkLogListExt, WorkLogState};
+use crate::state::days_off::{DayOffKind, DaysOff};
 use anyhow::Result;
 use chrono::{Datelike, NaiveDate};
 use cli_table::{format::Justify, Cell, CellStruct, Style, Table};
@@ -12,7 +14,16 @@ pub trait Render {
         tasks: WorkLogList,
         show_weekends: bool,
         holiday_map: Option<HolidayMap>,
+        days_off: &DaysOff,
+        schedule: &Schedule,
     ) -> Result<String>;
+    fn balance(
+        period: DateRange,
+        tasks: &WorkLogList,
+        holiday_map: &HolidayMap,
+        days_off: &DaysOff,
+        schedule: &Schedule,
+    ) -> String;
     fn works_on(tasks: Vec<Task>) -> String;
 }
 
@@ -24,30 +35,58 @@ impl Render for Calendar {
         tasks: WorkLogList,
         show_weekends: bool,
         holiday_map: Option<HolidayMap>,
+        days_off: &DaysOff,
+        schedule: &Schedule,
     ) -> Result<String> {
+        let today = chrono::Local::now().naive_local().date();
         let weekday_limit = if show_weekends { WEEKDAYS.len() } else { 5 };
         let holiday_map = holiday_map.unwrap_or_default();
         let table = range
             .days(show_weekends)
             .chunks(weekday_limit)
             .map(|week| {
-                week.iter()
+                let mut row: Vec<CellStruct> = week
+                    .iter()
                     .map(|day| {
+                        let holiday = holiday_map
+                            .get(&day.format("%Y-%m-%d").to_string())
+                            .cloned();
+                        let delta = (*day <= today).then(|| {
+                            tasks.counted_on(*day) as i64
+                                - expected_on(*day, &tasks, &holiday_map, days_off, schedule) as i64
+                        });
                         render_cell(
                             *day,
                             &tasks.get_by_day(*day),
-                            holiday_map
-                                .get(&day.format("%Y-%m-%d").to_string())
-                                .cloned(),
+                            holiday,
+                            days_off.get(*day),
+                            tasks.counted_on(*day),
+                            delta,
                         )
                     })
-                    .collect()
+                    .collect();
+                let monday = week[0];
+                let week_total = week_balances(
+                    DateRange {
+                        from: monday,
+                        to: monday + chrono::Duration::days(6),
+                    },
+                    &tasks,
+                    &holiday_map,
+                    days_off,
+                    schedule,
+                    today,
+                )
+                .pop();
+                row.push(render_week_total(week_total));
+                row
             })
             .collect::<Vec<Vec<CellStruct>>>()
             .table()
             .title(
                 WEEKDAYS[0..weekday_limit]
                     .iter()
+                    .chain(["Total"].iter())
                     .map(|&day| day.cell().justify(Justify::Center))
                     .collect::<Vec<_>>(),
             )
@@ -56,6 +95,45 @@ impl Render for Calendar {
         Ok(table.display()?.to_string())
     }
 
+    fn balance(
+        period: DateRange,
+        tasks: &WorkLogList,
+        holiday_map: &HolidayMap,
+        days_off: &DaysOff,
+        schedule: &Schedule,
+    ) -> String {
+        let today = chrono::Local::now().naive_local().date();
+        let weeks = week_balances(period, tasks, holiday_map, days_off, schedule, today);
+        let logged: u64 = weeks.iter().map(|w| w.logged).sum();
+        let expected: u64 = weeks.iter().map(|w| w.expected).sum();
+        let delta: i64 = weeks.iter().map(|w| w.delta()).sum();
+
+        let mut lines: Vec<String> = weeks
+            .iter()
+            .filter(|w| w.started || w.logged > 0)
+            .map(|w| {
+                if w.started {
+                    format!(
+                        "Week {}: {} of {} ({})",
+                        w.week,
+                        format_time(w.logged).green(),
+                        format_time(w.expected),
+                        colored_delta(w.delta())
+                    )
+                } else {
+                    format!("Week {}: {}", w.week, format_time(w.logged).dimmed())
+                }
+            })
+            .collect();
+        lines.push(format!(
+            "Total: {} of {}",
+            colored_time(logged, delta < 0).bold(),
+            format_time(expected)
+        ));
+        lines.push(format!("Balance: {}", colored_delta(delta).bold()));
+        format!("{}\n", lines.join("\n"))
+    }
+
     fn works_on(tasks: Vec<Task>) -> String {
         format!(
             "Actually you work on:\n{}",
@@ -68,7 +146,141 @@ impl Render for Calendar {
     }
 }
 
-fn render_cell(day: NaiveDate, tasks: &WorkLogList, holiday: Option<String>) -> CellStruct {
+/// Expected time of the day, nothing on holidays and days off. A day off
+/// logged to the absence task keeps its expected time, as the worklog counts.
+fn expected_on(
+    day: NaiveDate,
+    tasks: &WorkLogList,
+    holiday_map: &HolidayMap,
+    days_off: &DaysOff,
+    schedule: &Schedule,
+) -> u64 {
+    let absence_logged = || {
+        schedule
+            .absence_task
+            .as_ref()
+            .is_some_and(|task| tasks.get_by_day(day).iter().any(|w| &w.task == task))
+    };
+    if holiday_map.contains_key(&day.format("%Y-%m-%d").to_string())
+        || (days_off.get(day).is_some() && !absence_logged())
+    {
+        0
+    } else {
+        schedule.expected_on(day)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct WeekBalance {
+    week: u32,
+    /// Logged over the whole week, days after today included
+    logged: u64,
+    /// Logged and expected up to today, making up the balance
+    logged_to_date: u64,
+    expected: u64,
+    /// Whether the week has begun by today
+    started: bool,
+}
+
+impl WeekBalance {
+    fn delta(&self) -> i64 {
+        self.logged_to_date as i64 - self.expected as i64
+    }
+}
+
+/// Logged time per ISO week of the period, with the expected time and the
+/// balance counted up to `today`.
+fn week_balances(
+    period: DateRange,
+    tasks: &WorkLogList,
+    holiday_map: &HolidayMap,
+    days_off: &DaysOff,
+    schedule: &Schedule,
+    today: NaiveDate,
+) -> Vec<WeekBalance> {
+    let mut weeks: Vec<WeekBalance> = Vec::new();
+    for day in period {
+        let week = day.iso_week().week();
+        if weeks.last().map(|w| w.week) != Some(week) {
+            weeks.push(WeekBalance {
+                week,
+                logged: 0,
+                logged_to_date: 0,
+                expected: 0,
+                started: false,
+            });
+        }
+        let current = weeks.last_mut().expect("week was just pushed");
+        let logged = tasks.counted_on(day);
+        current.logged += logged;
+        if day <= today {
+            current.logged_to_date += logged;
+            current.expected += expected_on(day, tasks, holiday_map, days_off, schedule);
+            current.started = true;
+        }
+    }
+    weeks
+}
+
+fn colored_delta(seconds: i64) -> colored::ColoredString {
+    match seconds {
+        0 => format_delta(seconds).normal(),
+        s if s < 0 => format_delta(seconds).red(),
+        _ => format_delta(seconds).green(),
+    }
+}
+
+/// Logged time, red while below what was expected.
+fn colored_total(logged: u64, expected: u64) -> colored::ColoredString {
+    colored_time(logged, logged < expected)
+}
+
+fn colored_time(seconds: u64, behind: bool) -> colored::ColoredString {
+    if behind {
+        format_time(seconds).red()
+    } else {
+        format_time(seconds).green()
+    }
+}
+
+/// Day total with the difference to the expected time, `None` for days
+/// without either.
+fn day_summary(total: u64, delta: Option<i64>) -> Option<String> {
+    match delta {
+        None if total == 0 => None,
+        None => Some(format_time(total).dimmed().to_string()),
+        Some(0) if total == 0 => None,
+        Some(0) => Some(format_time(total).green().to_string()),
+        Some(delta) => Some(format!(
+            "{} ({})",
+            colored_total(total, (total as i64 - delta).max(0) as u64),
+            colored_delta(delta)
+        )),
+    }
+}
+
+fn render_week_total(week: Option<WeekBalance>) -> CellStruct {
+    match week {
+        Some(week) if week.started => format!(
+            "{}\nof {}",
+            colored_time(week.logged, week.delta() < 0).bold(),
+            format_time(week.expected)
+        ),
+        Some(week) if week.logged > 0 => format_time(week.logged).dimmed().to_string(),
+        _ => "-".to_string(),
+    }
+    .cell()
+    .justify(Justify::Center)
+}
+
+fn render_cell(
+    day: NaiveDate,
+    tasks: &WorkLogList,
+    holiday: Option<String>,
+    day_off: Option<DayOffKind>,
+    total: u64,
+    delta: Option<i64>,
+) -> CellStruct {
     // Style day number based on conditions
     let day_num = {
         let num = day.day().to_string().yellow();
@@ -79,6 +291,7 @@ fn render_cell(day: NaiveDate, tasks: &WorkLogList, holiday: Option<String>) ->
 
         match (holiday.is_some(), is_weekend, is_empty_today, is_today) {
             (true, ..) => num.cyan(),
+            _ if day_off.is_some() => num.magenta(),
             (_, true, ..) => num.dimmed(),
             (_, _, true, ..) => num.red(),
             (_, _, _, true) => num.blue(),
@@ -90,7 +303,12 @@ fn render_cell(day: NaiveDate, tasks: &WorkLogList, holiday: Option<String>) ->
     let mut task_text = tasks
         .iter()
         .map(|t| {
-            let task_display = t.task.green();
+            let task_display = match t.state {
+                WorkLogState::Logged => t.task.green(),
+                WorkLogState::Running => format!("▶ {}", t.task).yellow(),
+                WorkLogState::Pending => format!("+ {}", t.task).yellow(),
+                WorkLogState::Queued => format!("⇡ {}", t.task).blue(),
+            };
             match t.time_spent.as_str() {
                 "1d" => task_display.to_string(),
                 _ => format!("{} ({})", task_display, t.time_spent.dimmed()),
@@ -99,6 +317,18 @@ fn render_cell(day: NaiveDate, tasks: &WorkLogList, holiday: Option<String>) ->
         .collect::<Vec<_>>()
         .join("\n");
 
+    if let Some(kind) = day_off {
+        task_text = format!(
+            "{}{}",
+            kind.to_string().bold().magenta(),
+            if task_text.is_empty() {
+                String::new()
+            } else {
+                format!("\n{}", task_text)
+            }
+        );
+    }
+
     if let Some(holiday_name) = holiday {
         task_text = format!(
             "{}{}",
@@ -111,16 +341,20 @@ fn render_cell(day: NaiveDate, tasks: &WorkLogList, holiday: Option<String>) ->
         );
     }
 
-    let task_text = if task_text.is_empty() {
+    let mut task_text = if task_text.is_empty() {
         "-".to_string()
     } else {
         task_text
     };
 
+    if let Some(summary) = day_summary(total, delta) {
+        task_text = format!("{}\n{}", task_text, summary);
+    }
+
     let ansi_regex = regex::Regex::new(r"\x1B\[[0-9;]*[mK]").unwrap();
     let width = task_text
         .lines()
-        .map(|line| ansi_regex.replace_all(line, "").len())
+        .map(|line| ansi_regex.replace_all(line, "").chars().count())
         .max()
         .unwrap_or(0);
 
@@ -128,3 +362,160 @@ fn render_cell(day: NaiveDate, tasks: &WorkLogList, holiday: Option<String>) ->
         .cell()
         .justify(Justify::Center)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{worklog, WorkLog};
+
+    #[test]
+    fn test_render_totals() -> Result<()> {
+        let date = |day: u32| NaiveDate::from_ymd_opt(2025, 3, day).unwrap();
+        let worklog = |day: u32, seconds: u64| WorkLog {
+            time_spent: format_time(seconds),
+            ..worklog(day, "XX-1", seconds)
+        };
+        let schedule = Schedule {
+            expected: [8 * 3600, 8 * 3600, 8 * 3600, 8 * 3600, 8 * 3600, 0, 0],
+            absence_task: None,
+        };
+        let tasks = vec![worklog(3, 9000), worklog(3, 18000), worklog(4, 8 * 3600)];
+
+        let output = Calendar::render(
+            DateRange {
+                from: date(3),
+                to: date(9),
+            },
+            tasks.clone(),
+            false,
+            None,
+            &DaysOff::default(),
+            &schedule,
+        )?;
+        assert!(output.contains("Total"));
+        // 2h30m + 5h on the 3rd, 15h30m in the week
+        assert!(output.contains("7h30m"));
+        assert!(output.contains("15h30m"));
+
+        let balance = Calendar::balance(
+            DateRange {
+                from: date(3),
+                to: date(4),
+            },
+            &tasks,
+            &HolidayMap::new(),
+            &DaysOff::default(),
+            &schedule,
+        );
+        assert!(balance.contains("Total: "));
+        assert!(balance.contains("16h"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_week_balances() {
+        let date = |day: u32| NaiveDate::from_ymd_opt(2025, 3, day).unwrap();
+        let worklog = |day: u32, hours: u64| worklog(day, "XX-1", hours * 3600);
+        let schedule = Schedule {
+            expected: [8 * 3600, 8 * 3600, 8 * 3600, 8 * 3600, 4 * 3600, 0, 0],
+            absence_task: None,
+        };
+        // Wednesday 2025-03-05 is a holiday, Thursday 2025-03-06 a day off,
+        // Monday 2025-03-10 is "today"; time logged after it is in the totals only
+        let holiday_map = HolidayMap::from([("2025-03-05".to_string(), "Holiday".to_string())]);
+        let mut days_off = DaysOff::default();
+        days_off.add(date(6), DayOffKind::Vacation);
+        let tasks = vec![
+            worklog(3, 8),
+            worklog(4, 9),
+            worklog(6, 8),
+            worklog(10, 6),
+            worklog(12, 2),
+            worklog(18, 3),
+        ];
+
+        let weeks = week_balances(
+            DateRange {
+                from: date(3),
+                to: date(30),
+            },
+            &tasks,
+            &holiday_map,
+            &days_off,
+            &schedule,
+            date(10),
+        );
+
+        assert_eq!(
+            weeks,
+            vec![
+                WeekBalance {
+                    week: 10,
+                    logged: 25 * 3600,
+                    logged_to_date: 25 * 3600,
+                    expected: 20 * 3600,
+                    started: true,
+                },
+                WeekBalance {
+                    week: 11,
+                    logged: 8 * 3600,
+                    logged_to_date: 6 * 3600,
+                    expected: 8 * 3600,
+                    started: true,
+                },
+                WeekBalance {
+                    week: 12,
+                    logged: 3 * 3600,
+                    logged_to_date: 0,
+                    expected: 0,
+                    started: false,
+                },
+                WeekBalance {
+                    week: 13,
+                    logged: 0,
+                    logged_to_date: 0,
+                    expected: 0,
+                    started: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_week_balances_with_logged_absence() {
+        let date = |day: u32| NaiveDate::from_ymd_opt(2025, 3, day).unwrap();
+        let worklog = |day: u32, task: &str| worklog(day, task, 8 * 3600);
+        let schedule = Schedule {
+            expected: [8 * 3600, 8 * 3600, 8 * 3600, 8 * 3600, 8 * 3600, 0, 0],
+            absence_task: Some("HR-1".to_string()),
+        };
+        // Days off on Monday, logged to the absence task, and Tuesday, not logged
+        let mut days_off = DaysOff::default();
+        days_off.add(date(3), DayOffKind::Vacation);
+        days_off.add(date(4), DayOffKind::Sick);
+        let tasks = vec![worklog(3, "HR-1"), worklog(5, "XX-1")];
+
+        let weeks = week_balances(
+            DateRange {
+                from: date(3),
+                to: date(5),
+            },
+            &tasks,
+            &HolidayMap::new(),
+            &days_off,
+            &schedule,
+            date(5),
+        );
+
+        assert_eq!(
+            weeks,
+            vec![WeekBalance {
+                week: 10,
+                logged: 16 * 3600,
+                logged_to_date: 16 * 3600,
+                expected: 16 * 3600,
+                started: true,
+            }]
+        );
+    }
+}